@@ -1,35 +1,42 @@
 use std::{
-    cell::Cell,
     cell::RefCell,
     collections::HashMap,
     fmt::Debug,
-    rc::Rc,
+    future::Future,
+    ops::Range,
     sync::{
         atomic::{AtomicBool, AtomicI64},
-        Arc, Mutex, RwLock,
+        Arc, RwLock,
     },
+    time::{Duration, Instant},
 };
 
-use futures_channel::oneshot;
 use napi_derive_ohos::napi;
 use napi_ohos::{
-    bindgen_prelude::{CallbackContext, Function, JsObjectValue, Object, Unknown},
-    threadsafe_function::ThreadsafeFunctionCallMode,
+    bindgen_prelude::{Function, JsObjectValue, Object},
     Error, Result,
 };
 use ohos_arkui_binding::XComponent;
 use ohos_display_binding::default_display_scaled_density;
-use ohos_ime_binding::IME;
-use ohos_xcomponent_binding::RawWindow;
+use ohos_xcomponent_binding::{on_frame_change, RawWindow};
+use ohos_xcomponent_sys::{
+    OH_NativeXComponent_RegisterOnFrameCallback, OH_NativeXComponent_UnregisterOnFrameCallback,
+};
 
 use crate::{
-    get_helper, get_main_thread_env, get_permission_request_tsfn,
+    call_helper, call_permission_tsfn, get_helper, get_main_thread_env,
+    get_permission_request_tsfn, get_permission_setting_tsfn, ime_options, install_panic_hook,
+    is_main_thread, permission_codes, request_permission_on_main_thread,
     resource::{
         resource_manager as global_resource_manager,
         set_resource_manager as set_global_resource_manager,
     },
-    unknown_to_permission_promise, AbilityError, AvoidArea, AvoidAreaType, Configuration, Event,
-    OpenHarmonyWaker, PermissionRequest, PermissionRequestCode, PermissionRequestOutput, Rect,
+    run_on_main_thread, set_ime_cursor_area, set_ime_options, set_ime_surrounding_text,
+    set_permission_request_timeout, spawn_local, AbilityError, AvoidArea, AvoidAreaType,
+    BackGestureEvent, BackHandlerId, BackHandlers, Clipboard, Configuration, ControlFlow, Event,
+    FrameRate, FrameStats, FrameStatsCollector, FrameStatsConfig, HelperBridge, Ime, ImeOptions,
+    IntervalInfo, KeyboardState, MemoryLevel, MemoryTrimCallbacks, MemoryTrimId, Modifiers,
+    OpenHarmonyWaker, Permission, PermissionRequest, PermissionRequestCode, PermissionStatus, Rect,
     ResourceManager, WAKER,
 };
 
@@ -108,6 +115,17 @@ pub struct OpenHarmonyAppInner {
     pub(crate) window_rect: Rect,
     pub(crate) avoid_areas: HashMap<AvoidAreaType, AvoidArea>,
     pub(crate) init_context: AbilityInitContext,
+
+    control_flow: ControlFlow,
+    redraw_requested: bool,
+    frame_callback_armed: bool,
+    frame_rate: Option<FrameRate>,
+    frame_stats: Option<FrameStatsCollector>,
+
+    pub(crate) keyboard: KeyboardState,
+    keyboard_visible: bool,
+    keyboard_height: i32,
+    memory_level: MemoryLevel,
 }
 
 impl PartialEq for OpenHarmonyAppInner {
@@ -164,6 +182,16 @@ impl OpenHarmonyAppInner {
             window_rect: Default::default(),
             avoid_areas: HashMap::new(),
             init_context: AbilityInitContext::default(),
+            control_flow: ControlFlow::default(),
+            redraw_requested: false,
+            frame_callback_armed: false,
+            frame_rate: None,
+            frame_stats: None,
+
+            keyboard: KeyboardState::default(),
+            keyboard_visible: false,
+            keyboard_height: 0,
+            memory_level: MemoryLevel::Normal,
         }
     }
 
@@ -190,12 +218,138 @@ impl OpenHarmonyAppInner {
         self.configuration.clone()
     }
 
-    pub fn set_frame_rate(&self, min: i32, max: i32, expected: i32) {
-        if let Some(xcomponent) = self.xcomponent.as_ref() {
-            xcomponent
-                .native_xcomponent()
-                .set_frame_rate(min, max, expected)
-                .expect("Failed to set frame rate");
+    /// Set the expected frame rate range.
+    /// The value is remembered and applied again whenever the surface is recreated,
+    /// before the surface exists it is only stored. A rate the surface rejects keeps the previous one.
+    pub fn set_frame_rate<R: Into<FrameRate>>(&mut self, rate: R) -> Result<()> {
+        let rate = rate.into();
+        rate.range()
+            .validate()
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        let previous = self.frame_rate.replace(rate);
+        if self.raw_window.is_some() {
+            // a rejected rate isn't kept, the surface still runs at the previous one
+            self.apply_frame_rate()
+                .inspect_err(|_| self.frame_rate = previous)?;
+        }
+        Ok(())
+    }
+
+    pub fn frame_rate(&self) -> Option<FrameRate> {
+        self.frame_rate
+    }
+
+    /// Apply the remembered frame rate to the current XComponent
+    pub(crate) fn apply_frame_rate(&self) -> Result<()> {
+        let (Some(rate), Some(xcomponent)) = (self.frame_rate, self.xcomponent.as_ref()) else {
+            return Ok(());
+        };
+        let range = rate.range();
+        xcomponent
+            .native_xcomponent()
+            .set_frame_rate(range.min, range.max, range.expected)
+    }
+
+    /// Apply the remembered frame rate to a recreated surface.
+    /// A rate the surface rejects is forgotten, so `frame_rate()` reports the default it runs at.
+    pub(crate) fn reapply_frame_rate(&mut self) {
+        if let Err(err) = self.apply_frame_rate() {
+            log_warn!("Failed to restore the frame rate: {}", err);
+            self.frame_rate = None;
+        }
+    }
+
+    pub fn control_flow(&self) -> ControlFlow {
+        self.control_flow
+    }
+
+    pub fn set_control_flow(&mut self, control_flow: ControlFlow) {
+        self.control_flow = control_flow;
+        self.sync_frame_callback();
+    }
+
+    /// Ask for a single `Event::WindowRedraw` on the next vsync.
+    pub fn request_redraw(&mut self) {
+        self.redraw_requested = true;
+        self.sync_frame_callback();
+    }
+
+    /// Consume the redraw decision for the current vsync.
+    pub(crate) fn take_redraw(&mut self, now: Instant) -> bool {
+        match self.control_flow {
+            ControlFlow::Continuous => {
+                self.redraw_requested = false;
+                true
+            }
+            ControlFlow::Wait => std::mem::take(&mut self.redraw_requested),
+            ControlFlow::WaitUntil(deadline) => {
+                if now >= deadline {
+                    self.control_flow = ControlFlow::Wait;
+                    self.redraw_requested = false;
+                    true
+                } else {
+                    std::mem::take(&mut self.redraw_requested)
+                }
+            }
+        }
+    }
+
+    /// Register or unregister the XComponent frame callback so that it only
+    /// fires while a redraw can actually be delivered.
+    /// The closure itself is stored by `render`, this only toggles the native registration.
+    pub(crate) fn sync_frame_callback(&mut self) {
+        let needs_frame = self.redraw_requested || self.control_flow.needs_vsync();
+        if needs_frame == self.frame_callback_armed {
+            return;
+        }
+        let Some(xcomponent) = self.xcomponent.as_ref() else {
+            return;
+        };
+        let raw = xcomponent.native_xcomponent().raw();
+        let ret = unsafe {
+            if needs_frame {
+                OH_NativeXComponent_RegisterOnFrameCallback(raw, Some(on_frame_change))
+            } else {
+                OH_NativeXComponent_UnregisterOnFrameCallback(raw)
+            }
+        };
+        if ret == 0 {
+            self.frame_callback_armed = needs_frame;
+            if !needs_frame {
+                if let Some(collector) = self.frame_stats.as_mut() {
+                    collector.interrupt();
+                }
+            }
+        }
+    }
+
+    /// Mark the frame callback as registered after `render` installed it.
+    pub(crate) fn set_frame_callback_armed(&mut self, armed: bool) {
+        self.frame_callback_armed = armed;
+    }
+
+    pub fn enable_frame_stats(&mut self, config: FrameStatsConfig) {
+        self.frame_stats = Some(FrameStatsCollector::new(config));
+    }
+
+    pub fn disable_frame_stats(&mut self) {
+        self.frame_stats = None;
+    }
+
+    pub fn frame_stats(&self) -> Option<FrameStats> {
+        self.frame_stats
+            .as_ref()
+            .map(|collector| collector.snapshot(self.frame_rate.map(|rate| rate.range())))
+    }
+
+    /// Feed a vsync into the stats collector, returns a snapshot when a periodic report is due.
+    pub(crate) fn record_frame(&mut self, info: &IntervalInfo) -> Option<FrameStats> {
+        let frame_rate = self.frame_rate.map(|rate| rate.range());
+        let collector = self.frame_stats.as_mut()?;
+        if collector.record(info) {
+            Some(collector.snapshot(frame_rate))
+        } else {
+            None
         }
     }
 
@@ -215,6 +369,40 @@ impl OpenHarmonyAppInner {
         self.avoid_areas.clone()
     }
 
+    pub fn is_keyboard_visible(&self) -> bool {
+        self.keyboard_visible
+    }
+
+    pub fn keyboard_height(&self) -> i32 {
+        self.keyboard_height
+    }
+
+    pub fn memory_level(&self) -> MemoryLevel {
+        self.memory_level
+    }
+
+    /// Update the soft keyboard state and the cached keyboard avoid area, returns `true` when it changed
+    pub(crate) fn update_keyboard_visibility(&mut self, visible: bool, height: i32) -> bool {
+        let height = if visible { height.max(0) } else { 0 };
+        if self.keyboard_visible == visible && self.keyboard_height == height {
+            return false;
+        }
+        self.keyboard_visible = visible;
+        self.keyboard_height = height;
+
+        let window_rect = self.window_rect;
+        if let Some(area) = self.avoid_areas.get_mut(&AvoidAreaType::Keyboard) {
+            area.visible = visible;
+            if !visible {
+                area.bottom_rect = Rect::default();
+            } else if area.bottom_rect.height != height {
+                area.bottom_rect =
+                    Rect::new(0, window_rect.height - height, window_rect.width, height);
+            }
+        }
+        true
+    }
+
     pub fn native_window(&self) -> Option<RawWindow> {
         self.raw_window
     }
@@ -223,6 +411,10 @@ impl OpenHarmonyAppInner {
         default_display_scaled_density()
     }
 
+    pub fn modifiers(&self) -> Modifiers {
+        self.keyboard.modifiers()
+    }
+
     pub fn init_context(&self) -> AbilityInitContext {
         self.init_context.clone()
     }
@@ -258,15 +450,16 @@ impl OpenHarmonyAppInner {
 }
 
 type EventLoop = Arc<RefCell<Option<Box<dyn FnMut(Event) + Sync + Send>>>>;
-type BackPressInterceptor = Arc<RefCell<Option<Box<dyn FnMut() -> bool + Sync + Send>>>>;
+type BackHandlerStack = Arc<RefCell<BackHandlers>>;
+type MemoryTrimRegistry = Arc<RefCell<MemoryTrimCallbacks>>;
 
 #[derive(Clone)]
 pub struct OpenHarmonyApp {
     pub(crate) inner: Arc<RwLock<OpenHarmonyAppInner>>,
     pub(crate) event_loop: EventLoop,
-    pub(crate) back_press_interceptor: BackPressInterceptor,
-    pub(crate) ime: Arc<RefCell<Option<IME>>>,
-    is_keyboard_show: Arc<Mutex<bool>>,
+    pub(crate) back_handlers: BackHandlerStack,
+    pub(crate) memory_trim_callbacks: MemoryTrimRegistry,
+    pub(crate) ime: Arc<RefCell<Option<Ime>>>,
 }
 
 impl Debug for OpenHarmonyApp {
@@ -313,10 +506,11 @@ impl OpenHarmonyApp {
             #[allow(clippy::arc_with_non_send_sync)]
             event_loop: Arc::new(RefCell::new(None)),
             #[allow(clippy::arc_with_non_send_sync)]
-            back_press_interceptor: Arc::new(RefCell::new(None)),
+            back_handlers: Arc::new(RefCell::new(BackHandlers::default())),
+            #[allow(clippy::arc_with_non_send_sync)]
+            memory_trim_callbacks: Arc::new(RefCell::new(MemoryTrimCallbacks::default())),
             #[allow(clippy::arc_with_non_send_sync)]
             ime: Arc::new(RefCell::new(None)),
-            is_keyboard_show: Arc::new(Mutex::new(false)),
         }
     }
 
@@ -328,11 +522,29 @@ impl OpenHarmonyApp {
         self.inner.read().unwrap().load()
     }
 
-    pub fn set_frame_rate(&self, min: i32, max: i32, expected: i32) {
-        self.inner
-            .read()
-            .unwrap()
-            .set_frame_rate(min, max, expected);
+    /// Set the expected frame rate, either a preset or a custom `(min, max, expected)` range.
+    /// Returns an error when the range is invalid or the XComponent rejects it.
+    pub fn set_frame_rate<R: Into<FrameRate>>(&self, rate: R) -> Result<()> {
+        self.inner.write().unwrap().set_frame_rate(rate)
+    }
+
+    /// Get the frame rate last passed to `set_frame_rate`
+    pub fn frame_rate(&self) -> Option<FrameRate> {
+        self.inner.read().unwrap().frame_rate()
+    }
+
+    /// Start collecting frame pacing statistics from the XComponent frame callback.
+    pub fn enable_frame_stats(&self, config: FrameStatsConfig) {
+        self.inner.write().unwrap().enable_frame_stats(config);
+    }
+
+    pub fn disable_frame_stats(&self) {
+        self.inner.write().unwrap().disable_frame_stats();
+    }
+
+    /// Get the current frame statistics, `None` if collection is not enabled.
+    pub fn frame_stats(&self) -> Option<FrameStats> {
+        self.inner.read().unwrap().frame_stats()
     }
 
     #[doc(hidden)]
@@ -372,27 +584,128 @@ impl OpenHarmonyApp {
             .set_resource_manager(resource_manager);
     }
 
-    pub fn show_keyboard(&self) {
-        let _guard = self
-            .is_keyboard_show
-            .lock()
-            .expect("Failed to lock is_keyboard_show");
-        if let Some(ime) = self.ime.borrow().as_ref() {
-            ime.show_keyboard();
+    /// Attach a text editor to the input method and show the soft keyboard.
+    /// The input method is only created here, apps without text fields never attach one.
+    pub fn start_text_input(&self) {
+        self.ime
+            .borrow_mut()
+            .get_or_insert_with(Ime::new)
+            .show_keyboard();
+    }
+
+    /// Hide the soft keyboard and release the input method, IME events stop until the next `start_text_input`
+    pub fn stop_text_input(&self) {
+        // drop outside of the borrow, detaching may call back into the app
+        let ime = self.ime.borrow_mut().take();
+        if let Some(ime) = ime {
+            ime.hide_keyboard();
         }
     }
+
+    pub fn is_text_input_active(&self) -> bool {
+        self.ime.borrow().is_some()
+    }
+
+    /// Show the soft keyboard, starts text input when it isn't active
+    pub fn show_keyboard(&self) {
+        self.start_text_input();
+    }
+
+    /// Hide the soft keyboard, text input stays active and a hardware keyboard can still type
     pub fn hide_keyboard(&self) {
-        let _guard = self
-            .is_keyboard_show
-            .lock()
-            .expect("Failed to lock is_keyboard_show");
         if let Some(ime) = self.ime.borrow().as_ref() {
             ime.hide_keyboard();
         }
     }
+
+    /// Whether the soft keyboard is on screen
+    pub fn is_keyboard_visible(&self) -> bool {
+        self.inner.read().unwrap().is_keyboard_visible()
+    }
+
+    /// Height of the soft keyboard in px, 0 when it is hidden
+    pub fn keyboard_height(&self) -> i32 {
+        self.inner.read().unwrap().keyboard_height()
+    }
+
+    /// Record a keyboard state change and notify the event loop when it differs from the current one.
+    /// `None` keeps the last known height, e.g. for IME status callbacks which carry no height.
+    pub(crate) fn set_keyboard_visibility(&self, visible: bool, height: Option<i32>) {
+        let changed = {
+            let mut inner = self.inner.write().unwrap();
+            let height = height.unwrap_or(inner.keyboard_height);
+            inner
+                .update_keyboard_visibility(visible, height)
+                .then_some(inner.keyboard_height)
+        };
+        if let Some(height) = changed {
+            if let Some(ref mut h) = *self.event_loop.borrow_mut() {
+                h(Event::KeyboardVisibilityChanged { visible, height })
+            }
+        }
+    }
+
+    /// Get the keyboard options used for text input
+    pub fn ime_options(&self) -> ImeOptions {
+        ime_options()
+    }
+
+    /// Set the keyboard layout and Enter key for text input, applied to the shown keyboard immediately
+    pub fn set_ime_options(&self, options: ImeOptions) {
+        set_ime_options(self.ime.borrow().as_ref(), options);
+    }
+
+    /// Set the caret rect relative to the XComponent in px, so the keyboard can place its candidate window
+    pub fn set_ime_cursor_area(&self, area: Rect) {
+        let screen_area = {
+            let inner = self.inner.read().unwrap();
+            Rect {
+                left: inner.window_rect.left + inner.rect.left + area.left,
+                top: inner.window_rect.top + inner.rect.top + area.top,
+                ..area
+            }
+        };
+        set_ime_cursor_area(self.ime.borrow().as_ref(), screen_area);
+    }
+
+    /// Set the text around the caret, `selection` is a byte range of `text` and empty for a plain caret.
+    /// The IME uses it for predictions and for the text it reads around the cursor.
+    pub fn set_ime_surrounding_text(&self, text: &str, selection: Range<usize>) {
+        set_ime_surrounding_text(self.ime.borrow().as_ref(), text, selection);
+    }
+
     pub fn create_waker(&self) -> OpenHarmonyWaker {
         self.inner.read().unwrap().create_waker()
     }
+
+    /// Get the current redraw control flow
+    pub fn control_flow(&self) -> ControlFlow {
+        self.inner.read().unwrap().control_flow()
+    }
+
+    /// Set when `Event::WindowRedraw` is delivered.
+    /// Use `ControlFlow::Wait` together with `request_redraw` for static UIs.
+    pub fn set_control_flow(&self, control_flow: ControlFlow) {
+        if is_main_thread() {
+            self.inner.write().unwrap().set_control_flow(control_flow);
+        } else {
+            self.inner.write().unwrap().control_flow = control_flow;
+            self.create_waker().wake();
+        }
+    }
+
+    /// Request a single `Event::WindowRedraw` on the next vsync.
+    /// Can be called from any thread, off the main thread the frame callback
+    /// is armed through the waker.
+    pub fn request_redraw(&self) {
+        if is_main_thread() {
+            self.inner.write().unwrap().request_redraw();
+        } else {
+            self.inner.write().unwrap().redraw_requested = true;
+            self.create_waker().wake();
+        }
+    }
+
     pub fn config(&self) -> Configuration {
         self.inner.read().unwrap().config()
     }
@@ -418,7 +731,10 @@ impl OpenHarmonyApp {
         let get_window_avoid_area = helper_object
             .get_named_property::<Function<'_, i32, Object<'_>>>("getWindowAvoidArea")
             .ok()?;
-        let options = get_window_avoid_area.call(i32::from(area_type)).ok()?;
+        let options = get_window_avoid_area
+            .call(i32::from(area_type))
+            .inspect_err(|err| log_warn!("helper.getWindowAvoidArea failed: {}", err))
+            .ok()?;
         parse_avoid_area_options(options)
     }
 
@@ -462,14 +778,73 @@ impl OpenHarmonyApp {
         self.inner.read().unwrap().scale()
     }
 
-    /// Exit current app with code
+    /// Run `f` on the main thread from any thread
+    pub fn run_on_main_thread<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        run_on_main_thread(f)
+    }
+
+    /// Run a future on the main thread, must be called on the main thread
+    pub fn spawn_local<F>(&self, future: F) -> Result<()>
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        spawn_local(future)
+    }
+
+    /// Spawn a future on the main thread, can be called from any thread
+    #[cfg(feature = "async")]
+    pub fn spawn<F>(&self, future: F) -> crate::JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        crate::spawn(future)
+    }
+
+    pub fn is_main_thread(&self) -> bool {
+        is_main_thread()
+    }
+
+    /// Call methods of the ArkTS helper, including methods added by the app
+    pub fn helper(&self) -> HelperBridge {
+        HelperBridge
+    }
+
+    /// Get the system pasteboard
+    pub fn clipboard(&self) -> Clipboard {
+        Clipboard
+    }
+
+    /// Get modifier keys currently held on the hardware keyboard
+    pub fn modifiers(&self) -> Modifiers {
+        self.inner.read().unwrap().modifiers()
+    }
+
+    /// Opt in to panic reporting: panics caught in host callbacks are logged with their backtrace,
+    /// written to `base_path/crash` and reported with `Event::Crashed`. Only the first call installs the hook.
+    pub fn install_panic_hook(&self) {
+        install_panic_hook(self)
+    }
+
+    /// Exit current app with code, from other threads the exit is posted to the main thread
     pub fn exit(&self, code: i32) {
+        if !is_main_thread() {
+            let app = self.clone();
+            if let Err(err) = run_on_main_thread(move || app.exit(code)) {
+                log_error!("Failed to post exit to the main thread: {}", err);
+            }
+            return;
+        }
         self.inner.read().unwrap().exit(code).unwrap();
     }
 
     /// Request one or more runtime permissions through ArkTS helper.
     /// Returns each requested permission and the corresponding request result code.
-    /// ! Don't call this function from main thread with block_on.
+    /// ! Don't call this function from main thread with block_on,
+    /// use `request_permission_with_callback` or `request_permission_with_event` there.
     pub async fn request_permission<P>(&self, permission: P) -> Result<Vec<PermissionRequestCode>>
     where
         P: Into<PermissionRequest>,
@@ -478,72 +853,105 @@ impl OpenHarmonyApp {
         let requested_permissions = request.permissions();
         let input = request.into_input();
 
-        let permission_tsfn = get_permission_request_tsfn().ok_or_else(|| {
-            Error::from_reason("requestPermission threadsafe function is not initialized")
-        })?;
+        let output =
+            call_permission_tsfn(get_permission_request_tsfn(), "requestPermission", input).await?;
 
-        let (tx, rx) = oneshot::channel::<Result<PermissionRequestOutput>>();
-        let status = permission_tsfn.call_with_return_value(
-            input,
-            ThreadsafeFunctionCallMode::NonBlocking,
-            move |result, _| {
-                match result {
-                    Ok(value) => {
-                        let tx_cell = Rc::new(Cell::new(Some(tx)));
-                        let tx_in_catch = tx_cell.clone();
-                        let promise = unknown_to_permission_promise(value)?;
-                        promise
-                            .then(move |ctx| {
-                                if let Some(sender) = tx_cell.replace(None) {
-                                    let _ = sender.send(Ok(ctx.value));
-                                }
-                                Ok(())
-                            })?
-                            .catch(move |ctx: CallbackContext<Unknown>| {
-                                if let Some(sender) = tx_in_catch.replace(None) {
-                                    let _ = sender.send(Err(ctx.value.into()));
-                                }
-                                Ok(())
-                            })?;
-                    }
-                    Err(err) => {
-                        let _ = tx.send(Err(err));
-                    }
-                }
-
-                Ok(())
-            },
-        );
+        permission_codes(requested_permissions, output)
+    }
 
-        if status != napi_ohos::Status::Ok {
-            return Err(Error::from_reason(format!(
-                "call requestPermission failed with status: {:?}",
-                status
-            )));
-        }
+    /// Fail `request_permission` and `request_permission_on_setting` after `timeout`,
+    /// `None` waits until the Promise settles or the ability is destroyed
+    pub fn set_permission_request_timeout(&self, timeout: Option<Duration>) {
+        set_permission_request_timeout(timeout);
+    }
 
-        let output = rx
-            .await
-            .map_err(|_| Error::from_reason("requestPermission callback receiver dropped"))??;
+    /// Ask the user again for permanently denied permissions with the system settings dialog.
+    /// Returns the updated status of each permission in order.
+    /// ! Don't call this function from main thread with block_on.
+    pub async fn request_permission_on_setting<P>(
+        &self,
+        permissions: Vec<P>,
+    ) -> Result<Vec<PermissionStatus>>
+    where
+        P: Into<Permission>,
+    {
+        let permissions: Vec<String> = permissions
+            .into_iter()
+            .map(|permission| permission.into().into())
+            .collect();
+        let requested = permissions.len();
+        let output = call_permission_tsfn(
+            get_permission_setting_tsfn(),
+            "requestPermissionOnSetting",
+            napi_ohos::Either::B(permissions),
+        )
+        .await?;
 
         let codes = match output {
-            napi_ohos::Either::A(code) => vec![code],
-            napi_ohos::Either::B(codes) => codes,
+            napi_ohos::Either::A(codes) => codes,
+            napi_ohos::Either::B(result) => result.auth_results,
         };
-
-        if requested_permissions.len() != codes.len() {
+        if codes.len() != requested {
             return Err(Error::from_reason(format!(
-                "requestPermission result length mismatch: requested {}, got {}",
-                requested_permissions.len(),
+                "requestPermissionOnSetting result length mismatch: requested {}, got {}",
+                requested,
                 codes.len()
             )));
         }
+        Ok(codes.into_iter().map(PermissionStatus::from).collect())
+    }
 
-        Ok(requested_permissions
-            .into_iter()
-            .zip(codes)
-            .map(|(permission, code)| PermissionRequestCode { permission, code })
-            .collect())
+    /// Open the settings page of this app, e.g. to let the user grant permissions manually
+    pub fn open_app_settings(&self) -> Result<()> {
+        call_helper("openAppSettings", ())
+    }
+
+    /// Query the current grant status without prompting, only works on the main thread.
+    pub fn check_permission<P: Into<Permission>>(&self, permission: P) -> Result<PermissionStatus> {
+        let permission: String = permission.into().into();
+        call_helper::<_, i32>("checkPermission", permission).map(PermissionStatus::from)
+    }
+
+    /// Request one or more runtime permissions from the main thread without blocking.
+    /// `callback` runs on the main thread with the result, a timeout or a destroyed ability is an `Err`.
+    pub fn request_permission_with_callback<P, F>(&self, permission: P, callback: F) -> Result<()>
+    where
+        P: Into<PermissionRequest>,
+        F: FnOnce(Result<Vec<PermissionRequestCode>>) + 'static,
+    {
+        let request = permission.into();
+        let requested_permissions = request.permissions();
+
+        request_permission_on_main_thread(request.into_input(), move |_, output| {
+            callback(output.and_then(|output| permission_codes(requested_permissions, output)))
+        })?;
+        Ok(())
+    }
+
+    /// Request one or more runtime permissions from the main thread without blocking.
+    /// The result is delivered as `Event::PermissionResult` with the returned request id.
+    pub fn request_permission_with_event<P>(&self, permission: P) -> Result<u64>
+    where
+        P: Into<PermissionRequest>,
+    {
+        let request = permission.into();
+        let requested_permissions = request.permissions();
+        let app = self.clone();
+
+        request_permission_on_main_thread(request.into_input(), move |request_id, output| {
+            let results = output
+                .and_then(|output| permission_codes(requested_permissions, output))
+                .map_err(|err| {
+                    log_warn!("requestPermission failed: {}", err);
+                    err.reason.clone()
+                });
+            if let Some(ref mut h) = *app.event_loop.borrow_mut() {
+                h(Event::PermissionResult {
+                    request_id,
+                    results,
+                })
+            }
+        })
     }
 
     pub fn run_loop<'a, F: FnMut(Event) + 'a>(&self, mut event_handle: F) {
@@ -565,25 +973,106 @@ impl OpenHarmonyApp {
     }
 
     /// Register back press interceptor. Return `true` to intercept back action, `false` to pass through.
+    /// It replaces the interceptor of the previous call and is asked after all pushed back handlers.
     pub fn on_back_press_intercept<'a, F: FnMut() -> bool + 'a>(&self, interceptor: F) {
         let static_handler = unsafe {
-            std::mem::transmute::<
-                Box<dyn FnMut() -> bool + 'a>,
-                Box<dyn FnMut() -> bool + 'static + Sync + Send>,
-            >(Box::new(interceptor))
+            std::mem::transmute::<Box<dyn FnMut() -> bool + 'a>, Box<dyn FnMut() -> bool + 'static>>(
+                Box::new(interceptor),
+            )
         };
 
-        self.back_press_interceptor.replace(Some(static_handler));
+        self.back_handlers
+            .borrow_mut()
+            .replace_interceptor(static_handler);
     }
 
-    /// Get back press interceptor result
+    /// Push a back handler, the last pushed handler is asked first.
+    /// Return `true` to consume the back action, `false` to pass it to the next handler.
+    /// To decide asynchronously, return `true` and call `perform_default_back` later.
+    pub fn push_back_handler<'a, F: FnMut() -> bool + 'a>(&self, handler: F) -> BackHandlerId {
+        let static_handler = unsafe {
+            std::mem::transmute::<Box<dyn FnMut() -> bool + 'a>, Box<dyn FnMut() -> bool + 'static>>(
+                Box::new(handler),
+            )
+        };
+
+        self.back_handlers.borrow_mut().push(static_handler)
+    }
+
+    /// Remove the last pushed back handler
+    pub fn pop_back_handler(&self) -> Option<BackHandlerId> {
+        self.back_handlers.borrow_mut().pop()
+    }
+
+    /// Remove a back handler, returns `false` when it was already removed
+    pub fn remove_back_handler(&self, id: BackHandlerId) -> bool {
+        self.back_handlers.borrow_mut().remove(id)
+    }
+
+    /// Run the system back action, i.e. move the ability to background
+    pub fn perform_default_back(&self) -> Result<()> {
+        call_helper("performBack", ())
+    }
+
+    /// Deliver `Event::BackPressed` and ask the back handlers from top to bottom
     /// Returns true to intercept back press, false to pass through
     pub fn get_back_press_interceptor(&self) -> bool {
-        self.back_press_interceptor
+        if let Some(ref mut h) = *self.event_loop.borrow_mut() {
+            h(Event::BackPressed)
+        }
+        let handlers = self.back_handlers.borrow().snapshot();
+        handlers.iter().any(|handler| (handler.borrow_mut())())
+    }
+
+    /// Deliver `Event::BackGesture` for a back swipe reported by the ArkTS component
+    #[doc(hidden)]
+    pub fn dispatch_back_gesture(&self, event: BackGestureEvent) {
+        if let Some(ref mut h) = *self.event_loop.borrow_mut() {
+            h(Event::BackGesture(event))
+        }
+    }
+
+    /// Deliver `BackGestureEvent::Committed`, then handle it like a back press
+    /// Returns true to intercept the back action, false to run the default one
+    #[doc(hidden)]
+    pub fn commit_back_gesture(&self) -> bool {
+        self.dispatch_back_gesture(BackGestureEvent::Committed);
+        self.get_back_press_interceptor()
+    }
+
+    /// Last memory pressure reported by the system, `Normal` until the first report
+    pub fn memory_level(&self) -> MemoryLevel {
+        self.inner.read().unwrap().memory_level()
+    }
+
+    /// Register a callback to release memory, e.g. texture or glyph caches.
+    /// Callbacks run on the main thread in registration order, before `Event::MemoryLevel`.
+    pub fn add_memory_trim_callback<F: FnMut(MemoryLevel) + 'static>(
+        &self,
+        callback: F,
+    ) -> MemoryTrimId {
+        self.memory_trim_callbacks
             .borrow_mut()
-            .as_mut()
-            .map(|h| h())
-            .unwrap_or(true)
+            .add(Box::new(callback))
+    }
+
+    /// Remove a trim callback, returns `false` when it was already removed
+    pub fn remove_memory_trim_callback(&self, id: MemoryTrimId) -> bool {
+        self.memory_trim_callbacks.borrow_mut().remove(id)
+    }
+
+    /// Record the level reported by onMemoryLevel, run the trim callbacks and emit `Event::MemoryLevel`
+    pub(crate) fn set_memory_level(&self, level: MemoryLevel) {
+        self.inner.write().unwrap().memory_level = level;
+
+        let callbacks = self.memory_trim_callbacks.borrow().snapshot();
+        for callback in callbacks {
+            (callback.borrow_mut())(level);
+        }
+
+        if let Some(ref mut h) = *self.event_loop.borrow_mut() {
+            h(Event::MemoryLevel(level))
+        }
     }
 }
 
@@ -1,9 +1,11 @@
 mod avoid;
+mod point;
 mod rect;
 mod rect_reason;
 mod size;
 
 pub use avoid::*;
+pub use point::*;
 pub use rect::*;
 pub use rect_reason::*;
 pub use size::*;
@@ -0,0 +1,34 @@
+/// A position, physical px or vp depending on where it's used
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Convert physical px to vp with the display scaled density
+    pub fn to_logical(&self, scale: f32) -> Point {
+        Point {
+            x: self.x / scale,
+            y: self.y / scale,
+        }
+    }
+
+    /// Convert vp to physical px with the display scaled density
+    pub fn to_physical(&self, scale: f32) -> Point {
+        Point {
+            x: self.x * scale,
+            y: self.y * scale,
+        }
+    }
+}
+
+impl From<(f32, f32)> for Point {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self { x, y }
+    }
+}
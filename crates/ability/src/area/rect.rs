@@ -1,3 +1,5 @@
+use crate::Size;
+
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Rect {
     pub top: i32,
@@ -5,3 +7,45 @@ pub struct Rect {
     pub width: i32,
     pub height: i32,
 }
+
+impl Rect {
+    pub fn new(left: i32, top: i32, width: i32, height: i32) -> Self {
+        Self {
+            top,
+            left,
+            width,
+            height,
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn right(&self) -> i32 {
+        self.left + self.width
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.top + self.height
+    }
+
+    /// Whether the point is inside of the rect, the right and bottom edges are excluded
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.left && x < self.right() && y >= self.top && y < self.bottom()
+    }
+}
+
+impl From<Size> for Rect {
+    fn from(value: Size) -> Self {
+        Self {
+            top: 0,
+            left: 0,
+            width: value.width,
+            height: value.height,
+        }
+    }
+}
@@ -1,5 +1,63 @@
-#[derive(Clone)]
+/// Size in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Size {
     pub width: i32,
     pub height: i32,
 }
+
+impl Size {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Convert to vp with the display scaled density
+    pub fn to_logical(&self, scale: f32) -> LogicalSize {
+        LogicalSize {
+            width: self.width as f32 / scale,
+            height: self.height as f32 / scale,
+        }
+    }
+}
+
+impl From<(i32, i32)> for Size {
+    fn from((width, height): (i32, i32)) -> Self {
+        Self { width, height }
+    }
+}
+
+impl From<Size> for (i32, i32) {
+    fn from(value: Size) -> Self {
+        (value.width, value.height)
+    }
+}
+
+/// Size in vp, which is physical pixels divided by the display scaled density
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LogicalSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl LogicalSize {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+
+    /// Convert to physical pixels with the display scaled density, rounded to the nearest pixel
+    pub fn to_physical(&self, scale: f32) -> Size {
+        Size {
+            width: (self.width * scale).round() as i32,
+            height: (self.height * scale).round() as i32,
+        }
+    }
+}
+
+impl From<(f32, f32)> for LogicalSize {
+    fn from((width, height): (f32, f32)) -> Self {
+        Self { width, height }
+    }
+}
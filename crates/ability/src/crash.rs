@@ -0,0 +1,151 @@
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::RefCell,
+    fmt, fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use napi_ohos::{Error, Result};
+
+use crate::{Event, OpenHarmonyApp};
+
+/// App receiving `Event::Crashed`, set by `install_panic_hook`
+static PANIC_APP: OnceLock<OpenHarmonyApp> = OnceLock::new();
+
+/// `base_path/crash`, resolved when the hook is installed so reporting doesn't touch the app state
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+thread_local! {
+    /// Last panic of this thread, only reported once `catch_panic` catches it.
+    /// A panic caught by other code leaves it here until the next panic replaces it.
+    static PENDING_CRASH: RefCell<Option<CrashReport>> = const { RefCell::new(None) };
+}
+
+/// A Rust panic captured by the panic hook
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub message: String,
+    /// `file:line:column` of the panic
+    pub location: Option<String>,
+    pub thread: String,
+    pub backtrace: String,
+    /// report file under `base_path/crash`, `None` if it couldn't be written
+    pub path: Option<PathBuf>,
+}
+
+impl fmt::Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "thread '{}' panicked at {}:",
+            self.thread,
+            self.location.as_deref().unwrap_or("<unknown>")
+        )?;
+        writeln!(f, "{}", self.message)?;
+        writeln!(f)?;
+        write!(f, "backtrace:\n{}", self.backtrace)
+    }
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+fn take_pending_crash() -> Option<CrashReport> {
+    PENDING_CRASH
+        .try_with(|pending| pending.borrow_mut().take())
+        .ok()
+        .flatten()
+}
+
+fn write_report(dir: &Path, report: &CrashReport) -> Option<PathBuf> {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let path = dir.join(format!("panic-{}.log", millis));
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&path, report.to_string()))
+        .inspect_err(|err| log_warn!("Failed to write crash report: {}", err))
+        .ok()?;
+    Some(path)
+}
+
+/// Install the panic hook once, later calls are ignored. The previous hook still runs afterwards.
+pub(crate) fn install_panic_hook(app: &OpenHarmonyApp) {
+    if PANIC_APP.set(app.clone()).is_err() {
+        return;
+    }
+    if let Some(path) = app.base_path() {
+        let _ = CRASH_DIR.set(PathBuf::from(path).join("crash"));
+    }
+    let previous = panic::take_hook();
+
+    // the backtrace is only available while the hook runs, the report is finished by `catch_panic`
+    panic::set_hook(Box::new(move |info| {
+        let current = thread::current();
+        let report = CrashReport {
+            message: payload_message(info.payload()),
+            location: info.location().map(|location| location.to_string()),
+            thread: current.name().unwrap_or("<unnamed>").to_string(),
+            backtrace: Backtrace::force_capture().to_string(),
+            path: None,
+        };
+        let _ = PENDING_CRASH.try_with(|pending| pending.borrow_mut().replace(report));
+
+        previous(info);
+    }));
+}
+
+/// Log, write and emit the report of a panic which reached `catch_panic`
+fn report_crash(mut report: CrashReport) {
+    let Some(app) = PANIC_APP.get() else {
+        return;
+    };
+    log_error!("{}", report);
+    report.path = CRASH_DIR.get().and_then(|dir| write_report(dir, &report));
+
+    // a panic in the handler itself is dropped, it must not unwind into the host
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(mut event_loop) = app.event_loop.try_borrow_mut() {
+            if let Some(ref mut h) = *event_loop {
+                h(Event::Crashed(report))
+            }
+        }
+    }));
+    if result.is_err() {
+        take_pending_crash();
+    }
+}
+
+/// Run a callback of the host, a panic becomes an `Err` which napi throws as a JS exception.
+/// Panics unwinding through the C callbacks would abort the process otherwise.
+/// After `install_panic_hook` the caught panic is also reported.
+#[doc(hidden)]
+pub fn catch_panic<T>(name: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    // every host callback passes here, so its events are grouped under the callback name
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("host_callback", name).entered();
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload_message(&*payload);
+        // the hook replaced the report of any earlier panic on this thread, so it's this one
+        if let Some(report) = take_pending_crash() {
+            report_crash(report);
+        }
+        Err(Error::from_reason(format!(
+            "Rust panic in {}: {}",
+            name, message
+        )))
+    })
+}
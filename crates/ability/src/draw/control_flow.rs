@@ -0,0 +1,23 @@
+use std::time::Instant;
+
+/// Controls when `Event::WindowRedraw` is delivered to the event loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ControlFlow {
+    /// Deliver a redraw on every vsync. This is the default.
+    #[default]
+    Continuous,
+    /// Only deliver a redraw after `OpenHarmonyApp::request_redraw` is called.
+    Wait,
+    /// Deliver a single redraw once the deadline is reached, or earlier if
+    /// `OpenHarmonyApp::request_redraw` is called.
+    /// After the deadline fires the control flow falls back to `Wait`.
+    WaitUntil(Instant),
+}
+
+impl ControlFlow {
+    /// Whether the frame callback has to stay registered for this control flow,
+    /// regardless of any pending redraw request.
+    pub(crate) fn needs_vsync(&self) -> bool {
+        !matches!(self, ControlFlow::Wait)
+    }
+}
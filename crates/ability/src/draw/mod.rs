@@ -1,3 +1,9 @@
+mod control_flow;
+mod software;
+
+pub use control_flow::*;
+pub use software::*;
+
 #[derive(Clone)]
 pub struct IntervalInfo {
     pub time_stamp: i64,
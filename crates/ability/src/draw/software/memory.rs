@@ -0,0 +1,91 @@
+use napi_ohos::{Error, Result};
+use ohos_xcomponent_binding::RawWindow;
+
+use super::PixelFormat;
+
+pub(super) struct Frame {
+    pub(super) width: u32,
+    pub(super) height: u32,
+    pub(super) stride: u32,
+    pixels: Vec<u32>,
+}
+
+impl Frame {
+    pub(super) fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    pub(super) fn pixels_mut(&mut self) -> &mut [u32] {
+        &mut self.pixels
+    }
+}
+
+/// In-memory double buffer used when not running on OpenHarmony
+pub(super) struct Backend {
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+    back: Vec<u32>,
+    front: Vec<u32>,
+}
+
+impl Backend {
+    pub(super) fn new(_window: RawWindow, format: PixelFormat) -> Self {
+        Self {
+            format,
+            width: 0,
+            height: 0,
+            back: vec![],
+            front: vec![],
+        }
+    }
+
+    pub(super) fn resize(&mut self, width: u32, height: u32) -> Result<()> {
+        if width == 0 || height == 0 {
+            return Err(Error::from_reason(
+                "SoftwarePresenter size must not be zero",
+            ));
+        }
+        let len = width as usize * height as usize;
+        self.width = width;
+        self.height = height;
+        self.back = vec![0; len];
+        self.front = vec![0; len];
+        Ok(())
+    }
+
+    pub(super) fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub(super) fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    pub(super) fn lock(&mut self) -> Result<Frame> {
+        if self.width == 0 || self.height == 0 {
+            return Err(Error::from_reason(
+                "SoftwarePresenter::resize must be called before buffer_mut",
+            ));
+        }
+        Ok(Frame {
+            width: self.width,
+            height: self.height,
+            stride: self.width,
+            pixels: std::mem::take(&mut self.back),
+        })
+    }
+
+    pub(super) fn present(&mut self, frame: Frame) -> Result<()> {
+        self.back = std::mem::replace(&mut self.front, frame.pixels);
+        Ok(())
+    }
+
+    pub(super) fn abort(&mut self, frame: Frame) {
+        self.back = frame.pixels;
+    }
+
+    pub(super) fn presented(&self) -> &[u32] {
+        &self.front
+    }
+}
@@ -0,0 +1,142 @@
+//! CPU framebuffer presenter for the XComponent native window.
+//! On OpenHarmony the buffers come from `OHNativeWindow`, other targets use an
+//! in-memory buffer so that rendering code can run on the host.
+
+use std::ops::{Deref, DerefMut};
+
+use napi_ohos::Result;
+use ohos_xcomponent_binding::RawWindow;
+
+#[cfg(not(target_env = "ohos"))]
+mod memory;
+#[cfg(target_env = "ohos")]
+mod native;
+
+#[cfg(not(target_env = "ohos"))]
+use memory::{Backend, Frame};
+#[cfg(target_env = "ohos")]
+use native::{Backend, Frame};
+
+/// Memory layout of a single `u32` pixel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PixelFormat {
+    /// `0xAABBGGRR` in native endian, bytes are R, G, B, A in memory
+    #[default]
+    Rgba8888,
+    /// `0xAARRGGBB` in native endian, bytes are B, G, R, A in memory
+    Bgra8888,
+}
+
+/// Present CPU rendered pixels to a native window, similar to `softbuffer`.
+pub struct SoftwarePresenter {
+    backend: Backend,
+}
+
+impl SoftwarePresenter {
+    pub fn new(window: RawWindow) -> Self {
+        Self::with_format(window, PixelFormat::default())
+    }
+
+    pub fn with_format(window: RawWindow, format: PixelFormat) -> Self {
+        Self {
+            backend: Backend::new(window, format),
+        }
+    }
+
+    /// Set the buffer size in physical pixels, must be called before the first `buffer_mut`
+    /// and whenever the surface size changes.
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<()> {
+        self.backend.resize(width, height)
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.backend.size()
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.backend.format()
+    }
+
+    /// Lock the next buffer for drawing.
+    /// The buffer is returned to the window without being shown if it is dropped before `present`.
+    pub fn buffer_mut(&mut self) -> Result<SoftwareBuffer<'_>> {
+        let frame = self.backend.lock()?;
+        Ok(SoftwareBuffer {
+            backend: &mut self.backend,
+            frame: Some(frame),
+        })
+    }
+
+    /// Pixels of the last presented buffer.
+    /// Only available on the in-memory fallback.
+    #[cfg(not(target_env = "ohos"))]
+    pub fn presented(&self) -> &[u32] {
+        self.backend.presented()
+    }
+}
+
+/// A locked buffer, dereferences to the pixel rows.
+/// Rows are `stride()` pixels apart, only the first `width()` pixels of each row are visible.
+pub struct SoftwareBuffer<'a> {
+    backend: &'a mut Backend,
+    frame: Option<Frame>,
+}
+
+impl SoftwareBuffer<'_> {
+    pub fn width(&self) -> u32 {
+        self.frame().width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.frame().height
+    }
+
+    /// Row length in pixels
+    pub fn stride(&self) -> u32 {
+        self.frame().stride
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        self.backend.format()
+    }
+
+    /// Show the buffer on the window
+    pub fn present(mut self) -> Result<()> {
+        let frame = self
+            .frame
+            .take()
+            .expect("SoftwareBuffer frame already taken");
+        self.backend.present(frame)
+    }
+
+    fn frame(&self) -> &Frame {
+        self.frame
+            .as_ref()
+            .expect("SoftwareBuffer frame already taken")
+    }
+}
+
+impl Deref for SoftwareBuffer<'_> {
+    type Target = [u32];
+
+    fn deref(&self) -> &Self::Target {
+        self.frame().pixels()
+    }
+}
+
+impl DerefMut for SoftwareBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.frame
+            .as_mut()
+            .expect("SoftwareBuffer frame already taken")
+            .pixels_mut()
+    }
+}
+
+impl Drop for SoftwareBuffer<'_> {
+    fn drop(&mut self) {
+        if let Some(frame) = self.frame.take() {
+            self.backend.abort(frame);
+        }
+    }
+}
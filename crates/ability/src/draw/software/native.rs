@@ -0,0 +1,262 @@
+use std::{
+    os::raw::{c_int, c_void},
+    ptr,
+};
+
+use napi_ohos::{Error, Result};
+use ohos_xcomponent_binding::RawWindow;
+
+use super::PixelFormat;
+
+// native_window/external_window.h
+const SET_BUFFER_GEOMETRY: c_int = 0;
+const SET_FORMAT: c_int = 3;
+const SET_USAGE: c_int = 5;
+
+// native_buffer/native_buffer.h
+const NATIVEBUFFER_USAGE_CPU_READ: u64 = 1 << 0;
+const NATIVEBUFFER_USAGE_CPU_WRITE: u64 = 1 << 1;
+const NATIVEBUFFER_USAGE_MEM_DMA: u64 = 1 << 3;
+const NATIVEBUFFER_PIXEL_FMT_RGBA_8888: c_int = 12;
+const NATIVEBUFFER_PIXEL_FMT_BGRA_8888: c_int = 20;
+
+const FENCE_TIMEOUT_MS: c_int = 3000;
+
+#[repr(C)]
+struct OHNativeWindowBuffer {
+    _unused: [u8; 0],
+}
+
+#[repr(C)]
+struct BufferHandle {
+    fd: i32,
+    width: i32,
+    stride: i32,
+    height: i32,
+    size: i32,
+    format: i32,
+    usage: u64,
+    vir_addr: *mut c_void,
+    phy_addr: u64,
+    reserve_fds: u32,
+    reserve_ints: u32,
+}
+
+#[repr(C)]
+struct Region {
+    rects: *mut c_void,
+    rect_number: i32,
+}
+
+#[link(name = "native_window")]
+extern "C" {
+    fn OH_NativeWindow_NativeWindowHandleOpt(window: *mut c_void, code: c_int, ...) -> i32;
+    fn OH_NativeWindow_NativeWindowRequestBuffer(
+        window: *mut c_void,
+        buffer: *mut *mut OHNativeWindowBuffer,
+        fence_fd: *mut c_int,
+    ) -> i32;
+    fn OH_NativeWindow_GetBufferHandleFromNative(
+        buffer: *mut OHNativeWindowBuffer,
+    ) -> *mut BufferHandle;
+    fn OH_NativeWindow_NativeWindowFlushBuffer(
+        window: *mut c_void,
+        buffer: *mut OHNativeWindowBuffer,
+        fence_fd: c_int,
+        region: Region,
+    ) -> i32;
+    fn OH_NativeWindow_NativeWindowAbortBuffer(
+        window: *mut c_void,
+        buffer: *mut OHNativeWindowBuffer,
+    ) -> i32;
+}
+
+pub(super) struct Frame {
+    pub(super) width: u32,
+    pub(super) height: u32,
+    pub(super) stride: u32,
+    buffer: *mut OHNativeWindowBuffer,
+    mapped: *mut c_void,
+    mapped_size: usize,
+}
+
+impl Frame {
+    pub(super) fn pixels(&self) -> &[u32] {
+        unsafe { std::slice::from_raw_parts(self.mapped as *const u32, self.len()) }
+    }
+
+    pub(super) fn pixels_mut(&mut self) -> &mut [u32] {
+        unsafe { std::slice::from_raw_parts_mut(self.mapped as *mut u32, self.len()) }
+    }
+
+    fn len(&self) -> usize {
+        (self.stride as usize * self.height as usize).min(self.mapped_size / 4)
+    }
+
+    fn unmap(&self) {
+        unsafe {
+            libc::munmap(self.mapped, self.mapped_size);
+        }
+    }
+}
+
+pub(super) struct Backend {
+    window: RawWindow,
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+}
+
+impl Backend {
+    pub(super) fn new(window: RawWindow, format: PixelFormat) -> Self {
+        Self {
+            window,
+            format,
+            width: 0,
+            height: 0,
+        }
+    }
+
+    pub(super) fn resize(&mut self, width: u32, height: u32) -> Result<()> {
+        if width == 0 || height == 0 {
+            return Err(Error::from_reason(
+                "SoftwarePresenter size must not be zero",
+            ));
+        }
+        let format = match self.format {
+            PixelFormat::Rgba8888 => NATIVEBUFFER_PIXEL_FMT_RGBA_8888,
+            PixelFormat::Bgra8888 => NATIVEBUFFER_PIXEL_FMT_BGRA_8888,
+        };
+        let usage =
+            NATIVEBUFFER_USAGE_CPU_READ | NATIVEBUFFER_USAGE_CPU_WRITE | NATIVEBUFFER_USAGE_MEM_DMA;
+        let window = self.window.raw();
+        // each option is checked on its own, later ones don't run after a failure
+        let check = |operation: &str, ret: i32| -> Result<()> {
+            if ret != 0 {
+                return Err(Error::from_reason(format!(
+                    "Native window {} failed with code: {}",
+                    operation, ret
+                )));
+            }
+            Ok(())
+        };
+        check("SET_BUFFER_GEOMETRY", unsafe {
+            OH_NativeWindow_NativeWindowHandleOpt(
+                window,
+                SET_BUFFER_GEOMETRY,
+                width as c_int,
+                height as c_int,
+            )
+        })?;
+        check("SET_FORMAT", unsafe {
+            OH_NativeWindow_NativeWindowHandleOpt(window, SET_FORMAT, format)
+        })?;
+        check("SET_USAGE", unsafe {
+            OH_NativeWindow_NativeWindowHandleOpt(window, SET_USAGE, usage)
+        })?;
+        self.width = width;
+        self.height = height;
+        Ok(())
+    }
+
+    pub(super) fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub(super) fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    pub(super) fn lock(&mut self) -> Result<Frame> {
+        if self.width == 0 || self.height == 0 {
+            return Err(Error::from_reason(
+                "SoftwarePresenter::resize must be called before buffer_mut",
+            ));
+        }
+
+        let window = self.window.raw();
+        let mut buffer: *mut OHNativeWindowBuffer = ptr::null_mut();
+        let mut fence_fd: c_int = -1;
+        let ret = unsafe {
+            OH_NativeWindow_NativeWindowRequestBuffer(window, &mut buffer, &mut fence_fd)
+        };
+        if ret != 0 || buffer.is_null() {
+            return Err(Error::from_reason(format!(
+                "Request native window buffer failed with code: {}",
+                ret
+            )));
+        }
+
+        // The consumer may still read the buffer until the fence is signaled.
+        if fence_fd >= 0 {
+            let mut poll_fd = libc::pollfd {
+                fd: fence_fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            unsafe {
+                libc::poll(&mut poll_fd, 1, FENCE_TIMEOUT_MS);
+                libc::close(fence_fd);
+            }
+        }
+
+        let handle = unsafe { OH_NativeWindow_GetBufferHandleFromNative(buffer) };
+        if handle.is_null() {
+            unsafe { OH_NativeWindow_NativeWindowAbortBuffer(window, buffer) };
+            return Err(Error::from_reason("Get native window buffer handle failed"));
+        }
+        let handle = unsafe { &*handle };
+
+        let mapped_size = handle.size.max(0) as usize;
+        let mapped = unsafe {
+            libc::mmap(
+                handle.vir_addr,
+                mapped_size,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                handle.fd,
+                0,
+            )
+        };
+        if mapped == libc::MAP_FAILED {
+            unsafe { OH_NativeWindow_NativeWindowAbortBuffer(window, buffer) };
+            return Err(Error::from_reason("Map native window buffer failed"));
+        }
+
+        Ok(Frame {
+            width: handle.width.max(0) as u32,
+            height: handle.height.max(0) as u32,
+            // BufferHandle stride is in bytes
+            stride: (handle.stride.max(0) / 4) as u32,
+            buffer,
+            mapped,
+            mapped_size,
+        })
+    }
+
+    pub(super) fn present(&mut self, frame: Frame) -> Result<()> {
+        frame.unmap();
+        // an empty region marks the whole buffer as damaged
+        let region = Region {
+            rects: ptr::null_mut(),
+            rect_number: 0,
+        };
+        let ret = unsafe {
+            OH_NativeWindow_NativeWindowFlushBuffer(self.window.raw(), frame.buffer, -1, region)
+        };
+        if ret != 0 {
+            return Err(Error::from_reason(format!(
+                "Flush native window buffer failed with code: {}",
+                ret
+            )));
+        }
+        Ok(())
+    }
+
+    pub(super) fn abort(&mut self, frame: Frame) {
+        frame.unmap();
+        unsafe {
+            OH_NativeWindow_NativeWindowAbortBuffer(self.window.raw(), frame.buffer);
+        }
+    }
+}
@@ -1,6 +1,13 @@
+use std::time::Duration;
+
+use crate::FrameRateRange;
+
 #[derive(Debug)]
 pub enum AbilityError {
     OnlyRunWithMainThread(String),
+    InvalidFrameRate(FrameRateRange),
+    PermissionRequestTimeout(Duration),
+    AbilityDestroyed(String),
 }
 
 impl std::fmt::Display for AbilityError {
@@ -13,6 +20,27 @@ impl std::fmt::Display for AbilityError {
                     msg
                 )
             }
+            AbilityError::InvalidFrameRate(range) => {
+                write!(
+                    f,
+                    "OpenHarmonyAbilityError: invalid frame rate range, expected min <= expected <= max but got min={}, expected={}, max={}",
+                    range.min, range.expected, range.max
+                )
+            }
+            AbilityError::PermissionRequestTimeout(timeout) => {
+                write!(
+                    f,
+                    "OpenHarmonyAbilityError: permission request timed out after {:?}",
+                    timeout
+                )
+            }
+            AbilityError::AbilityDestroyed(msg) => {
+                write!(
+                    f,
+                    "OpenHarmonyAbilityError: {:?} cancelled because the ability was destroyed",
+                    msg
+                )
+            }
         }
     }
 }
@@ -1,8 +1,9 @@
 use std::fmt::{self, Debug, Formatter};
 
 use crate::{
-    AvoidAreaInfo, Configuration, ContentRect, InputEvent, IntervalInfo, SaveLoader, SaveSaver,
-    Size,
+    AvoidAreaInfo, BackGestureEvent, Configuration, ContentRect, CrashReport, FrameStats,
+    InputEvent, IntervalInfo, LogicalSize, MemoryLevel, PermissionRequestCode, SaveLoader,
+    SaveSaver, Size,
 };
 
 #[derive(Clone)]
@@ -17,10 +18,21 @@ pub enum Event<'a> {
     WindowDestroy,
 
     WindowRedraw(IntervalInfo),
-    /// window resize event
+    /// periodic frame pacing report
+    /// only emitted when `enable_frame_stats` is called with a `report_interval`
+    FrameStats(FrameStats),
+    /// window resize event, the size of the whole window in px
     /// alias window.on("windowSizeChange")
     /// https://developer.huawei.com/consumer/cn/doc/harmonyos-references-V5/js-apis-window-V5#onwindowsizechange7
-    WindowResize(Size),
+    WindowResized(Size),
+    /// render surface resize event
+    /// alias onSurfaceChanged for XComponent
+    /// `logical` is `physical` divided by `scale`, the display scaled density
+    SurfaceResized {
+        physical: Size,
+        logical: LogicalSize,
+        scale: f32,
+    },
     /// window rect change event
     /// alias window.on("windowRectChange")
     /// https://developer.huawei.com/consumer/cn/doc/harmonyos-references-V5/js-apis-window-V5#onwindowrectchange12
@@ -34,11 +46,10 @@ pub enum Event<'a> {
     /// alias onWindowConfigurationChanged
     /// https://developer.huawei.com/consumer/cn/doc/harmonyos-references-V5/js-apis-app-ability-environmentcallback-V5#environmentcallbackonconfigurationupdated
     ConfigChanged(Configuration),
-    /// low memory event
+    /// memory pressure event, delivered after the memory trim callbacks
     /// alias onMemoryLevel
-    /// it will execute when system memory is low(MEMORY_LEVEL_CRITICAL)
     /// https://developer.huawei.com/consumer/cn/doc/harmonyos-references-V5/js-apis-app-ability-environmentcallback-V5#environmentcallbackonmemorylevel
-    LowMemory,
+    MemoryLevel(MemoryLevel),
 
     /// WindowStateEventChanged
     /// https://developer.huawei.com/consumer/cn/doc/harmonyos-references-V5/js-apis-window-V5#onwindowstageevent9
@@ -85,10 +96,28 @@ pub enum Event<'a> {
     /// IME
     Input(InputEvent),
 
-    /// keyboard event
-    /// alias onKeyboardHeightChange
+    /// soft keyboard shown, hidden or resized, `height` is in px and 0 when hidden
+    /// merged from onKeyboardHeightChange, the IME keyboard status and the keyboard avoid area
     /// https://developer.huawei.com/consumer/cn/doc/harmonyos-references/arkts-apis-window-window#onkeyboardheightchange7
-    KeyboardEvent(i32),
+    KeyboardVisibilityChanged {
+        visible: bool,
+        height: i32,
+    },
+
+    /// back key or back gesture, delivered before the back handlers decide
+    /// alias onBackPress
+    BackPressed,
+    /// predictive back progress, only emitted when `DefaultXComponent.predictiveBack` is enabled
+    BackGesture(BackGestureEvent),
+
+    /// result of `request_permission_with_event`, a failed request carries the error message
+    PermissionResult {
+        request_id: u64,
+        results: std::result::Result<Vec<PermissionRequestCode>, String>,
+    },
+
+    /// a Rust panic caught in a host callback, only emitted after `install_panic_hook`
+    Crashed(CrashReport),
 
     UserEvent,
 }
@@ -99,11 +128,13 @@ impl<'a> Event<'a> {
             Event::WindowCreate => "WindowCreate",
             Event::WindowDestroy => "WindowDestroy",
             Event::WindowRedraw(_) => "WindowRedraw",
-            Event::WindowResize(_) => "WindowResize",
+            Event::FrameStats(_) => "FrameStats",
+            Event::WindowResized(_) => "WindowResized",
+            Event::SurfaceResized { .. } => "SurfaceResized",
             Event::ContentRectChange(_) => "ContentRectChange",
             Event::AvoidAreaChange(_) => "AvoidAreaChange",
             Event::ConfigChanged(_) => "ConfigChanged",
-            Event::LowMemory => "LowMemory",
+            Event::MemoryLevel(_) => "MemoryLevel",
             Event::Start => "Start",
             Event::GainedFocus => "GainedFocus",
             Event::LostFocus => "LostFocus",
@@ -116,8 +147,12 @@ impl<'a> Event<'a> {
             Event::SurfaceCreate => "SurfaceCreate",
             Event::SurfaceDestroy => "SurfaceDestroy",
             Event::Input(_) => "Input",
+            Event::BackPressed => "BackPressed",
+            Event::BackGesture(_) => "BackGesture",
+            Event::PermissionResult { .. } => "PermissionResult",
+            Event::Crashed(_) => "Crashed",
             Event::UserEvent => "UserEvent",
-            Event::KeyboardEvent(_) => "KeyboardEvent",
+            Event::KeyboardVisibilityChanged { .. } => "KeyboardVisibilityChanged",
         }
     }
 }
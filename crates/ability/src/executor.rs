@@ -0,0 +1,52 @@
+//! `Send` futures on the main thread.
+//! They're spawned from any thread and run by `spawn_local`, so they share its queue and waker
+//! and can await `request_permission`, `HelperBridge::call_async` or webview results without
+//! blocking the UI.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::oneshot;
+use napi_ohos::{Error, Result};
+
+use crate::{post_to_main_thread, spawn_local};
+
+/// Resolves with the output of a spawned future, dropping it doesn't cancel the task
+pub struct JoinHandle<T> {
+    rx: oneshot::Receiver<T>,
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx)
+            .poll(cx)
+            .map_err(|_| Error::from_reason("spawned task was dropped before it finished"))
+    }
+}
+
+/// Spawn a future on the main thread, can be called from any thread.
+/// If the task can't be queued or panics, the `JoinHandle` resolves with an `Err`.
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let task = async move {
+        let _ = tx.send(future.await);
+    };
+    let posted = post_to_main_thread(Box::new(move || {
+        if let Err(err) = spawn_local(task) {
+            log_error!("Failed to spawn task: {}", err);
+        }
+    }));
+    if let Err(err) = posted {
+        log_error!("Failed to spawn task: {}", err);
+    }
+    JoinHandle { rx }
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+/// Thresholds of `GestureRecognizer`, distances are in vp
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureConfig {
+    /// how far a pointer may move before a tap or long press turns into a pan
+    pub touch_slop: f32,
+    /// max distance between the two taps of a double tap
+    pub double_tap_slop: f32,
+    /// max time between the two taps of a double tap
+    pub double_tap_timeout: Duration,
+    /// how long a pointer must stay down to be a long press
+    pub long_press_timeout: Duration,
+    /// min release velocity in vp per second to emit a fling
+    pub min_fling_velocity: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            touch_slop: 8.0,
+            double_tap_slop: 32.0,
+            double_tap_timeout: Duration::from_millis(300),
+            long_press_timeout: Duration::from_millis(500),
+            min_fling_velocity: 50.0,
+        }
+    }
+}
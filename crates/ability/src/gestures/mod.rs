@@ -0,0 +1,371 @@
+//! Gesture recognition on top of `InputEvent::Pointer`.
+//! The recognizer only uses the timestamps carried by the events, so feeding it a synthetic
+//! sequence of `PointerEvent`s always produces the same gestures.
+
+use std::time::Duration;
+
+use crate::{InputEvent, Point, PointerEvent, PointerPhase};
+
+mod config;
+
+pub use config::*;
+
+/// Velocity is measured over the pointer samples of this window
+const VELOCITY_WINDOW_NS: i64 = 100_000_000;
+
+/// Positions are in vp, velocities in vp per second and angles in radians
+#[derive(Debug, Clone, PartialEq)]
+pub enum GestureEvent {
+    /// emitted for every single tap, also for the second tap of a double tap
+    Tap {
+        position: Point,
+    },
+    DoubleTap {
+        position: Point,
+    },
+    LongPress {
+        position: Point,
+    },
+    PanStart {
+        position: Point,
+    },
+    /// `delta` is the movement since the last pan event
+    Pan {
+        position: Point,
+        delta: Point,
+    },
+    PanEnd {
+        position: Point,
+        velocity: Point,
+    },
+    /// emitted after `PanEnd` when the release velocity reaches `min_fling_velocity`
+    Fling {
+        velocity: Point,
+    },
+    /// `scale` is the factor since the last pinch event
+    Pinch {
+        center: Point,
+        scale: f32,
+    },
+    /// `angle` is the clockwise rotation since the last rotate event
+    Rotate {
+        center: Point,
+        angle: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Idle,
+    /// one pointer is down and hasn't left the slop yet
+    Pending,
+    LongPressed,
+    Panning,
+    /// two or more pointers are down
+    Multi,
+    /// the gesture is finished, wait until all pointers are released
+    Consumed,
+}
+
+#[derive(Debug, Clone)]
+struct Tracked {
+    id: i32,
+    start: Point,
+    position: Point,
+    start_time: i64,
+}
+
+/// Turns pointer events into `GestureEvent`s.
+/// Call `update` periodically, e.g. on `WindowRedraw`, to detect long presses without moving.
+#[derive(Debug, Clone)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    mode: Mode,
+    pointers: Vec<Tracked>,
+    samples: Vec<(Point, i64)>,
+    last_tap: Option<(Point, i64)>,
+    span: f32,
+    angle: f32,
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            mode: Mode::Idle,
+            pointers: Vec::new(),
+            samples: Vec::new(),
+            last_tap: None,
+            span: 0.0,
+            angle: 0.0,
+        }
+    }
+
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Feed an input event, everything but `InputEvent::Pointer` is ignored
+    pub fn handle_input(&mut self, event: &InputEvent) -> Vec<GestureEvent> {
+        match event {
+            InputEvent::Pointer(pointer) => self.handle(pointer),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn handle(&mut self, event: &PointerEvent) -> Vec<GestureEvent> {
+        let mut out = Vec::new();
+        match event.phase {
+            PointerPhase::Down => self.on_down(event, &mut out),
+            PointerPhase::Move => self.on_move(event, &mut out),
+            PointerPhase::Up => self.on_up(event, &mut out),
+            PointerPhase::Cancel => self.on_cancel(event, &mut out),
+        }
+        out
+    }
+
+    /// Detect a long press, `timestamp` uses the same clock as `PointerEvent::timestamp`
+    pub fn update(&mut self, timestamp: i64) -> Vec<GestureEvent> {
+        let mut out = Vec::new();
+        self.check_long_press(timestamp, &mut out);
+        out
+    }
+
+    /// Forget all pointers without emitting any event
+    pub fn reset(&mut self) {
+        self.mode = Mode::Idle;
+        self.pointers.clear();
+        self.samples.clear();
+        self.last_tap = None;
+    }
+
+    fn on_down(&mut self, event: &PointerEvent, out: &mut Vec<GestureEvent>) {
+        self.pointers.retain(|p| p.id != event.id);
+        self.pointers.push(Tracked {
+            id: event.id,
+            start: event.position,
+            position: event.position,
+            start_time: event.timestamp,
+        });
+
+        match self.pointers.len() {
+            1 => {
+                self.mode = Mode::Pending;
+                self.samples.clear();
+                self.samples.push((event.position, event.timestamp));
+            }
+            2 if matches!(self.mode, Mode::Pending | Mode::Panning) => {
+                if self.mode == Mode::Panning {
+                    out.push(GestureEvent::PanEnd {
+                        position: self.pointers[0].position,
+                        velocity: Point::default(),
+                    });
+                }
+                self.mode = Mode::Multi;
+                self.span = self.current_span();
+                self.angle = self.current_angle();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_move(&mut self, event: &PointerEvent, out: &mut Vec<GestureEvent>) {
+        self.check_long_press(event.timestamp, out);
+
+        let Some(index) = self.pointers.iter().position(|p| p.id == event.id) else {
+            return;
+        };
+        let previous = self.pointers[index].position;
+        self.pointers[index].position = event.position;
+
+        match self.mode {
+            Mode::Pending => {
+                self.push_sample(event.position, event.timestamp);
+                let start = self.pointers[index].start;
+                if distance(start, event.position) > self.config.touch_slop {
+                    self.mode = Mode::Panning;
+                    out.push(GestureEvent::PanStart { position: start });
+                    out.push(GestureEvent::Pan {
+                        position: event.position,
+                        delta: sub(event.position, start),
+                    });
+                }
+            }
+            Mode::Panning => {
+                self.push_sample(event.position, event.timestamp);
+                out.push(GestureEvent::Pan {
+                    position: event.position,
+                    delta: sub(event.position, previous),
+                });
+            }
+            Mode::Multi if index < 2 => {
+                let center = self.current_center();
+                let span = self.current_span();
+                if self.span > 0.0 && span > 0.0 && span != self.span {
+                    out.push(GestureEvent::Pinch {
+                        center,
+                        scale: span / self.span,
+                    });
+                }
+                self.span = span;
+
+                let angle = self.current_angle();
+                let delta = normalize_angle(angle - self.angle);
+                if delta != 0.0 {
+                    out.push(GestureEvent::Rotate {
+                        center,
+                        angle: delta,
+                    });
+                }
+                self.angle = angle;
+            }
+            _ => {}
+        }
+    }
+
+    fn on_up(&mut self, event: &PointerEvent, out: &mut Vec<GestureEvent>) {
+        let Some(index) = self.pointers.iter().position(|p| p.id == event.id) else {
+            return;
+        };
+        let tracked = self.pointers.remove(index);
+
+        match self.mode {
+            Mode::Pending => {
+                let elapsed = event.timestamp - tracked.start_time;
+                if elapsed >= duration_ns(self.config.long_press_timeout) {
+                    out.push(GestureEvent::LongPress {
+                        position: tracked.start,
+                    });
+                } else {
+                    self.on_tap(event.position, event.timestamp, out);
+                }
+            }
+            Mode::Panning => {
+                self.push_sample(event.position, event.timestamp);
+                let velocity = self.velocity();
+                out.push(GestureEvent::PanEnd {
+                    position: event.position,
+                    velocity,
+                });
+                if length(velocity) >= self.config.min_fling_velocity {
+                    out.push(GestureEvent::Fling { velocity });
+                }
+            }
+            _ => {}
+        }
+
+        self.mode = if self.pointers.is_empty() {
+            Mode::Idle
+        } else {
+            Mode::Consumed
+        };
+    }
+
+    fn on_cancel(&mut self, event: &PointerEvent, out: &mut Vec<GestureEvent>) {
+        if self.mode == Mode::Panning {
+            out.push(GestureEvent::PanEnd {
+                position: event.position,
+                velocity: Point::default(),
+            });
+        }
+        self.mode = Mode::Idle;
+        self.pointers.clear();
+        self.samples.clear();
+    }
+
+    fn on_tap(&mut self, position: Point, timestamp: i64, out: &mut Vec<GestureEvent>) {
+        out.push(GestureEvent::Tap { position });
+
+        let double = self.last_tap.take().is_some_and(|(last, time)| {
+            timestamp - time <= duration_ns(self.config.double_tap_timeout)
+                && distance(last, position) <= self.config.double_tap_slop
+        });
+        if double {
+            out.push(GestureEvent::DoubleTap { position });
+        } else {
+            self.last_tap = Some((position, timestamp));
+        }
+    }
+
+    fn check_long_press(&mut self, timestamp: i64, out: &mut Vec<GestureEvent>) {
+        if self.mode != Mode::Pending {
+            return;
+        }
+        let Some(tracked) = self.pointers.first() else {
+            return;
+        };
+        if timestamp - tracked.start_time >= duration_ns(self.config.long_press_timeout) {
+            self.mode = Mode::LongPressed;
+            self.last_tap = None;
+            out.push(GestureEvent::LongPress {
+                position: tracked.start,
+            });
+        }
+    }
+
+    fn push_sample(&mut self, position: Point, timestamp: i64) {
+        self.samples.push((position, timestamp));
+        self.samples
+            .retain(|(_, time)| timestamp - time <= VELOCITY_WINDOW_NS);
+    }
+
+    fn velocity(&self) -> Point {
+        let (Some((first, start)), Some((last, end))) = (self.samples.first(), self.samples.last())
+        else {
+            return Point::default();
+        };
+        let seconds = (end - start) as f32 / 1_000_000_000.0;
+        if seconds <= 0.0 {
+            return Point::default();
+        }
+        let delta = sub(*last, *first);
+        Point::new(delta.x / seconds, delta.y / seconds)
+    }
+
+    fn current_center(&self) -> Point {
+        let (a, b) = (self.pointers[0].position, self.pointers[1].position);
+        Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+    }
+
+    fn current_span(&self) -> f32 {
+        distance(self.pointers[0].position, self.pointers[1].position)
+    }
+
+    fn current_angle(&self) -> f32 {
+        let delta = sub(self.pointers[1].position, self.pointers[0].position);
+        delta.y.atan2(delta.x)
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self::new(GestureConfig::default())
+    }
+}
+
+fn duration_ns(duration: Duration) -> i64 {
+    duration.as_nanos().min(i64::MAX as u128) as i64
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    Point::new(a.x - b.x, a.y - b.y)
+}
+
+fn length(p: Point) -> f32 {
+    p.x.hypot(p.y)
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    length(sub(a, b))
+}
+
+fn normalize_angle(angle: f32) -> f32 {
+    use std::f32::consts::PI;
+    if angle > PI {
+        angle - 2.0 * PI
+    } else if angle < -PI {
+        angle + 2.0 * PI
+    } else {
+        angle
+    }
+}
@@ -0,0 +1,92 @@
+use std::{cell::Cell, rc::Rc};
+
+use futures_channel::oneshot;
+use napi_ohos::{
+    bindgen_prelude::{
+        CallbackContext, FromNapiValue, JsValuesTupleIntoVec, PromiseRaw, Unknown,
+        ValidateNapiValue,
+    },
+    Error, JsValue, Result,
+};
+
+use crate::post_to_main_thread;
+
+use super::call_helper;
+
+type ResultSender<T> = Rc<Cell<Option<oneshot::Sender<Result<T>>>>>;
+
+/// Call methods of the ArkTS helper object passed to `render`.
+/// Add your own methods to the helper on the ArkTS side and call them by name.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HelperBridge;
+
+impl HelperBridge {
+    /// Call `helper[method](...args)`, only works on the main thread
+    pub fn call<Args, Ret>(&self, method: &str, args: Args) -> Result<Ret>
+    where
+        Args: JsValuesTupleIntoVec,
+        Ret: FromNapiValue,
+    {
+        call_helper(method, args)
+    }
+
+    /// Call `helper[method](...args)` on the main thread from any thread.
+    /// When the method returns a Promise, this resolves with the settled value.
+    /// A value that doesn't match `Ret` is an `Err`.
+    /// ! Don't call this function from main thread with block_on.
+    pub async fn call_async<Args, Ret>(&self, method: &str, args: Args) -> Result<Ret>
+    where
+        Args: JsValuesTupleIntoVec + Send + 'static,
+        Ret: FromNapiValue + ValidateNapiValue + Send + 'static,
+    {
+        let method = method.to_string();
+        let (tx, rx) = oneshot::channel::<Result<Ret>>();
+        post_to_main_thread(Box::new(move || {
+            let sender: ResultSender<Ret> = Rc::new(Cell::new(Some(tx)));
+            if let Err(err) = call_and_settle(&method, args, sender.clone()) {
+                send(&sender, Err(err));
+            }
+        }))?;
+
+        rx.await
+            .map_err(|_| Error::from_reason("helper call receiver dropped"))?
+    }
+}
+
+fn send<T>(sender: &ResultSender<T>, result: Result<T>) {
+    if let Some(sender) = sender.replace(None) {
+        let _ = sender.send(result);
+    }
+}
+
+fn call_and_settle<Args, Ret>(method: &str, args: Args, sender: ResultSender<Ret>) -> Result<()>
+where
+    Args: JsValuesTupleIntoVec,
+    Ret: FromNapiValue + ValidateNapiValue + 'static,
+{
+    let value = call_helper::<_, Unknown<'_>>(method, args)?;
+    if !value.is_promise()? {
+        let raw = value.value();
+        // Safety: `raw` is alive in the current scope and `validate` checks its type
+        // before `from_napi_value` reads it, a mismatched type is an `Err`.
+        let result = unsafe {
+            Ret::validate(raw.env, raw.value).and_then(|_| Ret::from_napi_value(raw.env, raw.value))
+        };
+        send(&sender, result);
+        return Ok(());
+    }
+
+    // Safety: checked with is_promise above.
+    let promise = unsafe { value.cast::<PromiseRaw<'_, Ret>>() }?;
+    let sender_in_catch = sender.clone();
+    promise
+        .then(move |ctx| {
+            send(&sender, Ok(ctx.value));
+            Ok(())
+        })?
+        .catch(move |ctx: CallbackContext<Unknown>| {
+            send(&sender_in_catch, Err(ctx.value.into()));
+            Ok(())
+        })?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use futures_channel::oneshot;
+use napi_ohos::{
+    bindgen_prelude::{CallbackContext, Function, JsObjectValue, PromiseRaw, Unknown},
+    threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+    Env, Error, Result, Status,
+};
+
+use crate::{catch_panic, get_helper, get_main_thread_env};
+
+use super::call_helper;
+
+type ClipboardReadCall<'a> = Function<'a, (), Unknown<'a>>;
+
+type ClipboardThreadsafeFunction = ThreadsafeFunction<(), Unknown<'static>, (), Status, false>;
+
+type ClipboardReadTsfn = LazyLock<RwLock<Option<Arc<ClipboardThreadsafeFunction>>>>;
+
+pub(crate) static CLIPBOARD_READ_TSFN: ClipboardReadTsfn = LazyLock::new(|| RwLock::new(None));
+
+/// System pasteboard, proxied to the ArkTS helper.
+/// The sync methods only run on the main thread, use `get_text_async` from other threads.
+/// A denied permission or failed read is an `Err`, `Ok(None)` means there is no such content.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Clipboard;
+
+impl Clipboard {
+    /// Read the plain text, reading may need `ohos.permission.READ_PASTEBOARD`
+    pub fn get_text(&self) -> Result<Option<String>> {
+        call_helper("getClipboardText", ())
+    }
+
+    pub fn set_text(&self, text: &str) -> Result<()> {
+        call_helper("setClipboardText", text.to_string())
+    }
+
+    /// Read the html text, reading may need `ohos.permission.READ_PASTEBOARD`
+    pub fn get_html(&self) -> Result<Option<String>> {
+        call_helper("getClipboardHtml", ())
+    }
+
+    /// Whether the pasteboard has any content, this doesn't need the read permission
+    pub fn has_content(&self) -> Result<bool> {
+        call_helper("hasClipboardContent", ())
+    }
+
+    /// Read the plain text through the async pasteboard API, the system may ask the user first.
+    /// ! Don't call this function from main thread with block_on.
+    pub async fn get_text_async(&self) -> Result<Option<String>> {
+        let read_tsfn = get_clipboard_read_tsfn().ok_or_else(|| {
+            Error::from_reason("getClipboardTextAsync threadsafe function is not initialized")
+        })?;
+
+        let (tx, rx) = oneshot::channel::<Result<Option<String>>>();
+        let status = read_tsfn.call_with_return_value(
+            (),
+            ThreadsafeFunctionCallMode::NonBlocking,
+            move |result, _| {
+                match result {
+                    Ok(value) => {
+                        let tx_cell = Rc::new(Cell::new(Some(tx)));
+                        let tx_in_catch = tx_cell.clone();
+                        // Safety: ArkTS helper.getClipboardTextAsync always returns a Promise<string | undefined>.
+                        let promise =
+                            unsafe { value.cast::<PromiseRaw<'static, Option<String>>>() }?;
+                        promise
+                            .then(move |ctx| {
+                                if let Some(sender) = tx_cell.replace(None) {
+                                    let _ = sender.send(Ok(ctx.value));
+                                }
+                                Ok(())
+                            })?
+                            .catch(move |ctx: CallbackContext<Unknown>| {
+                                if let Some(sender) = tx_in_catch.replace(None) {
+                                    let _ = sender.send(Err(ctx.value.into()));
+                                }
+                                Ok(())
+                            })?;
+                    }
+                    Err(err) => {
+                        let _ = tx.send(Err(err));
+                    }
+                }
+
+                Ok(())
+            },
+        );
+
+        if status != Status::Ok {
+            return Err(Error::from_reason(format!(
+                "call getClipboardTextAsync failed with status: {:?}",
+                status
+            )));
+        }
+
+        rx.await
+            .map_err(|_| Error::from_reason("getClipboardTextAsync callback receiver dropped"))?
+    }
+}
+
+/// Create clipboard read threadsafe function.
+/// The callback proxies to ArkTS helper.getClipboardTextAsync() and returns its Promise object.
+pub fn create_clipboard_read_tsfn(env: &Env) -> Result<Arc<ClipboardThreadsafeFunction>> {
+    let clipboard_read_callback: Function<'_, (), Unknown<'_>> =
+        env.create_function_from_closure("clipboard_read_callback", move |_ctx| {
+            catch_panic("clipboard_read_callback", || {
+                if let Some(env_ref) = get_main_thread_env().borrow().as_ref() {
+                    let helper = unsafe { get_helper() };
+                    let helper_borrow = helper.borrow();
+                    if let Some(helper_ref) = helper_borrow.as_ref() {
+                        let helper_obj = helper_ref.get_value(env_ref)?;
+                        let read_fn = helper_obj
+                            .get_named_property::<ClipboardReadCall<'_>>("getClipboardTextAsync")?;
+                        return read_fn.call(());
+                    }
+                }
+
+                Err(Error::from_reason(
+                    "Failed to call helper.getClipboardTextAsync from main thread",
+                ))
+            })
+        })?;
+
+    let tsfn = clipboard_read_callback
+        .build_threadsafe_function()
+        .callee_handled::<false>()
+        .build()?;
+
+    let tsfn_arc = Arc::new(tsfn);
+
+    {
+        let mut guard = (*CLIPBOARD_READ_TSFN)
+            .write()
+            .map_err(|_| Error::from_reason("Failed to write CLIPBOARD_READ_TSFN"))?;
+        guard.replace(tsfn_arc.clone());
+    }
+
+    Ok(tsfn_arc)
+}
+
+pub fn get_clipboard_read_tsfn() -> Option<Arc<ClipboardThreadsafeFunction>> {
+    (*CLIPBOARD_READ_TSFN)
+        .read()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(Arc::clone))
+}
@@ -1,12 +1,21 @@
 use std::{cell::RefCell, rc::Rc};
 
-use napi_ohos::{bindgen_prelude::ObjectRef, Env};
+use napi_ohos::{
+    bindgen_prelude::{FromNapiValue, Function, JsObjectValue, JsValuesTupleIntoVec, ObjectRef},
+    Env, Error, Result,
+};
 
+use crate::AbilityError;
+
+mod bridge;
+mod clipboard;
 mod permission;
 #[cfg(feature = "webview")]
 mod webview;
 mod window_info;
 
+pub use bridge::*;
+pub use clipboard::*;
 pub use permission::*;
 #[cfg(feature = "webview")]
 pub use webview::*;
@@ -38,3 +47,29 @@ pub fn set_main_thread_env(env: Env) {
 pub fn get_main_thread_env() -> Rc<RefCell<Option<Env>>> {
     MAIN_THREAD_ENV.with(Rc::clone)
 }
+
+/// Call a sync function of the ArkTS helper, only works on the main thread
+pub(crate) fn call_helper<Args, Return>(name: &str, args: Args) -> Result<Return>
+where
+    Args: JsValuesTupleIntoVec,
+    Return: FromNapiValue,
+{
+    let env = get_main_thread_env();
+    let env_borrow = env.borrow();
+    let Some(env_ref) = env_borrow.as_ref() else {
+        return Err(Error::from_reason(
+            AbilityError::OnlyRunWithMainThread(name.to_string()).to_string(),
+        ));
+    };
+    let helper = unsafe { get_helper() };
+    let helper_borrow = helper.borrow();
+    let helper_ref = helper_borrow
+        .as_ref()
+        .ok_or_else(|| Error::from_reason("ArkTS helper is not initialized"))?;
+    let helper_obj = helper_ref.get_value(env_ref)?;
+    let func = helper_obj
+        .get_named_property::<Function<'_, Args, Return>>(name)
+        .inspect_err(|err| log_warn!("helper.{} is not callable: {}", name, err))?;
+    func.call(args)
+        .inspect_err(|err| log_warn!("helper.{} failed: {}", name, err))
+}
@@ -1,15 +1,58 @@
-use std::sync::{Arc, LazyLock, RwLock};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, LazyLock, Mutex, MutexGuard, Once, RwLock,
+    },
+    time::{Duration, Instant},
+};
 
+use futures_channel::oneshot;
+use napi_derive_ohos::napi;
 use napi_ohos::{
-    bindgen_prelude::{Function, JsObjectValue, PromiseRaw, Unknown},
-    threadsafe_function::ThreadsafeFunction,
+    bindgen_prelude::{CallbackContext, Function, JsObjectValue, PromiseRaw, Unknown},
+    threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
     Either, Env, Error, Result, Status,
 };
 
-use crate::get_main_thread_env;
+use crate::{catch_panic, get_main_thread_env, spawn_local, AbilityError};
+
+/// Result codes of the ArkTS helper, granted, denied and invalid follow `authResults`
+const PERMISSION_GRANTED: i32 = 0;
+const PERMISSION_INVALID_REQUEST: i32 = 2;
+
+static PERMISSION_REQUEST_ID: AtomicU64 = AtomicU64::new(0);
+
+type PermissionResultSender = oneshot::Sender<Result<PermissionRequestOutput>>;
+
+/// A request waiting for its Promise, `method` names the helper function in errors
+struct PendingRequest {
+    method: String,
+    sender: PermissionResultSender,
+    deadline: Option<(Instant, Duration)>,
+}
+
+static PENDING_PERMISSION_REQUESTS: LazyLock<Mutex<HashMap<u64, PendingRequest>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Wakes the timeout thread when a request with a deadline is registered
+static PERMISSION_TIMEOUT_SIGNAL: Condvar = Condvar::new();
+static PERMISSION_TIMEOUT_THREAD: Once = Once::new();
+
+static PERMISSION_REQUEST_TIMEOUT: RwLock<Option<Duration>> = RwLock::new(None);
 
 pub type PermissionRequestInput = Either<String, Vec<String>>;
-pub type PermissionRequestOutput = Either<i32, Vec<i32>>;
+/// Codes of helper.requestPermissionOnSetting, or the result of helper.requestPermission
+pub type PermissionRequestOutput = Either<Vec<i32>, PermissionRequestResult>;
+
+/// Result of helper.requestPermission, both lists are in request order
+#[napi(object)]
+#[derive(Clone, Debug, Default)]
+pub struct PermissionRequestResult {
+    pub auth_results: Vec<i32>,
+    /// `false` when the system denied without showing the dialog
+    pub dialog_shown_results: Vec<bool>,
+}
 
 type PermissionRequestCall<'a> = Function<'a, PermissionRequestInput, Unknown<'a>>;
 
@@ -26,6 +69,104 @@ type PermissionRequestTsfn = LazyLock<RwLock<Option<Arc<PermissionThreadsafeFunc
 pub(crate) static PERMISSION_REQUEST_TSFN: PermissionRequestTsfn =
     LazyLock::new(|| RwLock::new(None));
 
+pub(crate) static PERMISSION_SETTING_TSFN: PermissionRequestTsfn =
+    LazyLock::new(|| RwLock::new(None));
+
+macro_rules! permissions {
+    ($($variant:ident => $name:literal,)*) => {
+        /// Common OpenHarmony permissions, use `Custom` for the others.
+        /// `Internet`, `Vibrate` are granted at install time and only need to be declared in module.json5.
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Permission {
+            $($variant,)*
+            /// full permission name, e.g. `ohos.permission.READ_MESSAGES`
+            Custom(String),
+        }
+
+        impl Permission {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $name,)*
+                    Self::Custom(permission) => permission,
+                }
+            }
+        }
+
+        impl From<&str> for Permission {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($name => Self::$variant,)*
+                    _ => Self::Custom(value.to_string()),
+                }
+            }
+        }
+    };
+}
+
+// one table for both directions of the name mapping
+permissions! {
+    Camera => "ohos.permission.CAMERA",
+    Microphone => "ohos.permission.MICROPHONE",
+    ApproximateLocation => "ohos.permission.APPROXIMATELY_LOCATION",
+    Location => "ohos.permission.LOCATION",
+    LocationInBackground => "ohos.permission.LOCATION_IN_BACKGROUND",
+    ReadImageVideo => "ohos.permission.READ_IMAGEVIDEO",
+    WriteImageVideo => "ohos.permission.WRITE_IMAGEVIDEO",
+    ReadAudio => "ohos.permission.READ_AUDIO",
+    WriteAudio => "ohos.permission.WRITE_AUDIO",
+    MediaLocation => "ohos.permission.MEDIA_LOCATION",
+    ReadContacts => "ohos.permission.READ_CONTACTS",
+    WriteContacts => "ohos.permission.WRITE_CONTACTS",
+    ReadCalendar => "ohos.permission.READ_CALENDAR",
+    WriteCalendar => "ohos.permission.WRITE_CALENDAR",
+    AccessBluetooth => "ohos.permission.ACCESS_BLUETOOTH",
+    ActivityMotion => "ohos.permission.ACTIVITY_MOTION",
+    ReadHealthData => "ohos.permission.READ_HEALTH_DATA",
+    DistributedDatasync => "ohos.permission.DISTRIBUTED_DATASYNC",
+    AppTrackingConsent => "ohos.permission.APP_TRACKING_CONSENT",
+    ReadPasteboard => "ohos.permission.READ_PASTEBOARD",
+    Internet => "ohos.permission.INTERNET",
+    Vibrate => "ohos.permission.VIBRATE",
+}
+
+impl From<String> for Permission {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<Permission> for String {
+    fn from(value: Permission) -> Self {
+        match value {
+            Permission::Custom(permission) => permission,
+            permission => permission.as_str().to_string(),
+        }
+    }
+}
+
+/// Grant status of a permission
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PermissionStatus {
+    Granted,
+    /// denied by the user in the dialog
+    Denied,
+    /// denied without showing the dialog because the user refused it before,
+    /// only `request_permission_on_setting` or the settings page can grant it
+    DeniedPermanently,
+    /// the request is invalid, e.g. not declared in module.json5 or not allowed for this app
+    Restricted,
+}
+
+impl From<i32> for PermissionStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            PERMISSION_GRANTED => Self::Granted,
+            PERMISSION_INVALID_REQUEST => Self::Restricted,
+            _ => Self::Denied,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PermissionRequest {
     Single(String),
@@ -72,33 +213,73 @@ impl From<Vec<&str>> for PermissionRequest {
     }
 }
 
+impl From<Permission> for PermissionRequest {
+    fn from(value: Permission) -> Self {
+        Self::Single(value.into())
+    }
+}
+
+impl From<Vec<Permission>> for PermissionRequest {
+    fn from(value: Vec<Permission>) -> Self {
+        Self::Multiple(value.into_iter().map(String::from).collect())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PermissionRequestCode {
     pub permission: String,
     pub code: i32,
+    /// whether the system showed the dialog for this permission
+    pub dialog_shown: bool,
+}
+
+impl PermissionRequestCode {
+    pub fn status(&self) -> PermissionStatus {
+        match PermissionStatus::from(self.code) {
+            PermissionStatus::Denied if !self.dialog_shown => PermissionStatus::DeniedPermanently,
+            status => status,
+        }
+    }
 }
 
 /// Create permission request threadsafe function.
 /// The callback proxies to ArkTS helper.requestPermission(permission) and returns its Promise object.
 pub fn create_permission_request_tsfn(env: &Env) -> Result<Arc<PermissionThreadsafeFunction>> {
+    create_permission_tsfn(env, "requestPermission", &PERMISSION_REQUEST_TSFN)
+}
+
+/// Create permission setting threadsafe function.
+/// The callback proxies to ArkTS helper.requestPermissionOnSetting(permissions) and returns its Promise object.
+pub fn create_permission_setting_tsfn(env: &Env) -> Result<Arc<PermissionThreadsafeFunction>> {
+    create_permission_tsfn(env, "requestPermissionOnSetting", &PERMISSION_SETTING_TSFN)
+}
+
+fn create_permission_tsfn(
+    env: &Env,
+    method: &'static str,
+    slot: &PermissionRequestTsfn,
+) -> Result<Arc<PermissionThreadsafeFunction>> {
     let permission_request_callback: Function<'_, PermissionRequestInput, Unknown<'_>> = env
         .create_function_from_closure("permission_request_callback", move |ctx| {
-            let permission = ctx.first_arg::<PermissionRequestInput>()?;
-
-            if let Some(env_ref) = get_main_thread_env().borrow().as_ref() {
-                let helper = unsafe { crate::get_helper() };
-                let helper_borrow = helper.borrow();
-                if let Some(helper_ref) = helper_borrow.as_ref() {
-                    let helper_obj = helper_ref.get_value(env_ref)?;
-                    let request_permission_fn = helper_obj
-                        .get_named_property::<PermissionRequestCall<'_>>("requestPermission")?;
-                    return request_permission_fn.call(permission);
+            catch_panic("permission_request_callback", || {
+                let permission = ctx.first_arg::<PermissionRequestInput>()?;
+
+                if let Some(env_ref) = get_main_thread_env().borrow().as_ref() {
+                    let helper = unsafe { crate::get_helper() };
+                    let helper_borrow = helper.borrow();
+                    if let Some(helper_ref) = helper_borrow.as_ref() {
+                        let helper_obj = helper_ref.get_value(env_ref)?;
+                        let request_permission_fn =
+                            helper_obj.get_named_property::<PermissionRequestCall<'_>>(method)?;
+                        return request_permission_fn.call(permission);
+                    }
                 }
-            }
 
-            Err(Error::from_reason(
-                "Failed to call helper.requestPermission from main thread",
-            ))
+                Err(Error::from_reason(format!(
+                    "Failed to call helper.{} from main thread",
+                    method
+                )))
+            })
         })?;
 
     let tsfn = permission_request_callback
@@ -109,9 +290,9 @@ pub fn create_permission_request_tsfn(env: &Env) -> Result<Arc<PermissionThreads
     let tsfn_arc = Arc::new(tsfn);
 
     {
-        let mut guard = (*PERMISSION_REQUEST_TSFN)
+        let mut guard = slot
             .write()
-            .map_err(|_| Error::from_reason("Failed to write PERMISSION_REQUEST_TSFN"))?;
+            .map_err(|_| Error::from_reason(format!("Failed to write {} tsfn", method)))?;
         guard.replace(tsfn_arc.clone());
     }
 
@@ -125,9 +306,291 @@ pub fn get_permission_request_tsfn() -> Option<Arc<PermissionThreadsafeFunction>
         .and_then(|guard| guard.as_ref().map(Arc::clone))
 }
 
+pub fn get_permission_setting_tsfn() -> Option<Arc<PermissionThreadsafeFunction>> {
+    (*PERMISSION_SETTING_TSFN)
+        .read()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(Arc::clone))
+}
+
 pub fn unknown_to_permission_promise(
     value: Unknown<'static>,
 ) -> Result<PromiseRaw<'static, PermissionRequestOutput>> {
-    // Safety: ArkTS helper.requestPermission always returns a Promise<number | number[]>.
+    // Safety: the ArkTS permission helpers always return a Promise, its value is validated
+    // as `PermissionRequestOutput` when it settles.
     unsafe { value.cast::<PromiseRaw<'static, PermissionRequestOutput>>() }
 }
+
+pub(crate) fn next_permission_request_id() -> u64 {
+    PERMISSION_REQUEST_ID.fetch_add(1, Ordering::SeqCst)
+}
+
+/// Pair the requested permissions with the result codes of helper.requestPermission
+pub(crate) fn permission_codes(
+    permissions: Vec<String>,
+    output: PermissionRequestOutput,
+) -> Result<Vec<PermissionRequestCode>> {
+    let (codes, dialog_shown) = match output {
+        // codes without dialog flags count as shown
+        Either::A(codes) => {
+            let dialog_shown = vec![true; codes.len()];
+            (codes, dialog_shown)
+        }
+        Either::B(result) => (result.auth_results, result.dialog_shown_results),
+    };
+
+    if permissions.len() != codes.len() || codes.len() != dialog_shown.len() {
+        return Err(Error::from_reason(format!(
+            "requestPermission result length mismatch: requested {}, got {} codes and {} dialog flags",
+            permissions.len(),
+            codes.len(),
+            dialog_shown.len()
+        )));
+    }
+
+    Ok(permissions
+        .into_iter()
+        .zip(codes.into_iter().zip(dialog_shown))
+        .map(|(permission, (code, dialog_shown))| PermissionRequestCode {
+            permission,
+            code,
+            dialog_shown,
+        })
+        .collect())
+}
+
+/// Call ArkTS helper.requestPermission directly, so it must run on the main thread.
+/// `callback` runs on the main thread once the Promise settles, the request times out
+/// or the ability is destroyed, with the id of the request which is also returned.
+pub(crate) fn request_permission_on_main_thread<F>(
+    input: PermissionRequestInput,
+    callback: F,
+) -> Result<u64>
+where
+    F: FnOnce(u64, Result<PermissionRequestOutput>) + 'static,
+{
+    let env = get_main_thread_env();
+    let env_borrow = env.borrow();
+    let Some(env_ref) = env_borrow.as_ref() else {
+        return Err(Error::from_reason(
+            AbilityError::OnlyRunWithMainThread("requestPermission".to_string()).to_string(),
+        ));
+    };
+    let helper = unsafe { crate::get_helper() };
+    let helper_borrow = helper.borrow();
+    let helper_ref = helper_borrow
+        .as_ref()
+        .ok_or_else(|| Error::from_reason("ArkTS helper is not initialized"))?;
+    let helper_obj = helper_ref.get_value(env_ref)?;
+    let request_permission_fn =
+        helper_obj.get_named_property::<PermissionRequestCall<'_>>("requestPermission")?;
+    let value = request_permission_fn.call(input)?;
+    // Safety: ArkTS helper.requestPermission always returns a Promise, its value is validated
+    // as `PermissionRequestOutput` when it settles.
+    let promise = unsafe { value.cast::<PromiseRaw<'_, PermissionRequestOutput>>() }?;
+
+    // tracked like the async requests, so timeouts and a destroyed ability settle it too
+    let (tx, rx) = oneshot::channel::<Result<PermissionRequestOutput>>();
+    let pending = PendingPermissionRequest::register("requestPermission", tx);
+    let request_id = pending.0;
+    promise
+        .then(move |ctx| {
+            complete_permission_request(request_id, Ok(ctx.value));
+            Ok(())
+        })?
+        .catch(move |ctx: CallbackContext<Unknown>| {
+            complete_permission_request(request_id, Err(ctx.value.into()));
+            Ok(())
+        })?;
+
+    spawn_local(async move {
+        let output = rx.await.unwrap_or_else(|_| {
+            Err(Error::from_reason(
+                "requestPermission callback receiver dropped",
+            ))
+        });
+        drop(pending);
+        callback(request_id, output);
+    })?;
+    Ok(request_id)
+}
+
+/// Call a permission threadsafe function and wait for the Promise it returns.
+/// The request is tracked until the Promise settles, it times out, the ability is destroyed
+/// or the returned future is dropped.
+/// ! Don't call this function from main thread with block_on.
+pub(crate) async fn call_permission_tsfn(
+    tsfn: Option<Arc<PermissionThreadsafeFunction>>,
+    method: &str,
+    input: PermissionRequestInput,
+) -> Result<PermissionRequestOutput> {
+    let permission_tsfn = tsfn.ok_or_else(|| {
+        Error::from_reason(format!("{} threadsafe function is not initialized", method))
+    })?;
+
+    let (tx, rx) = oneshot::channel::<Result<PermissionRequestOutput>>();
+    let pending = PendingPermissionRequest::register(method, tx);
+    let request_id = pending.0;
+    let status = permission_tsfn.call_with_return_value(
+        input,
+        ThreadsafeFunctionCallMode::NonBlocking,
+        move |result, _| {
+            match result {
+                Ok(value) => {
+                    let promise = unknown_to_permission_promise(value)?;
+                    promise
+                        .then(move |ctx| {
+                            complete_permission_request(request_id, Ok(ctx.value));
+                            Ok(())
+                        })?
+                        .catch(move |ctx: CallbackContext<Unknown>| {
+                            complete_permission_request(request_id, Err(ctx.value.into()));
+                            Ok(())
+                        })?;
+                }
+                Err(err) => {
+                    complete_permission_request(request_id, Err(err));
+                }
+            }
+
+            Ok(())
+        },
+    );
+
+    if status != Status::Ok {
+        return Err(Error::from_reason(format!(
+            "call {} failed with status: {:?}",
+            method, status
+        )));
+    }
+
+    let output = rx
+        .await
+        .map_err(|_| Error::from_reason(format!("{} callback receiver dropped", method)))?;
+    drop(pending);
+    output
+}
+
+/// How long `request_permission` waits for the dialog, `None` waits until the ability is destroyed
+pub fn set_permission_request_timeout(timeout: Option<Duration>) {
+    *PERMISSION_REQUEST_TIMEOUT
+        .write()
+        .expect("Failed to write PERMISSION_REQUEST_TIMEOUT") = timeout;
+}
+
+pub fn permission_request_timeout() -> Option<Duration> {
+    *PERMISSION_REQUEST_TIMEOUT
+        .read()
+        .expect("Failed to read PERMISSION_REQUEST_TIMEOUT")
+}
+
+fn pending_permission_requests() -> MutexGuard<'static, HashMap<u64, PendingRequest>> {
+    PENDING_PERMISSION_REQUESTS
+        .lock()
+        .expect("Failed to lock PENDING_PERMISSION_REQUESTS")
+}
+
+/// Resolve a pending request, later results of the same request are ignored
+fn complete_permission_request(request_id: u64, result: Result<PermissionRequestOutput>) {
+    let request = pending_permission_requests().remove(&request_id);
+    if let Some(request) = request {
+        let _ = request.sender.send(result);
+    }
+}
+
+/// Fail all pending requests, their Promises can't settle anymore
+pub(crate) fn cancel_pending_permission_requests() {
+    let requests: Vec<_> = pending_permission_requests().drain().collect();
+    for (_, request) in requests {
+        let _ = request.sender.send(Err(Error::from_reason(
+            AbilityError::AbilityDestroyed(request.method).to_string(),
+        )));
+    }
+}
+
+/// Shared by all requests, fails the expired ones and sleeps until the nearest deadline
+fn run_permission_timeouts() {
+    let mut pending = pending_permission_requests();
+    loop {
+        let now = Instant::now();
+        let expired: Vec<_> = pending
+            .iter()
+            .filter(|(_, request)| {
+                request
+                    .deadline
+                    .is_some_and(|(deadline, _)| deadline <= now)
+            })
+            .map(|(request_id, _)| *request_id)
+            .collect();
+        let expired: Vec<_> = expired
+            .into_iter()
+            .filter_map(|request_id| pending.remove(&request_id))
+            .collect();
+        if !expired.is_empty() {
+            // the waiting futures may lock the registry again when they wake up
+            drop(pending);
+            for request in expired {
+                if let Some((_, timeout)) = request.deadline {
+                    let _ = request.sender.send(Err(Error::from_reason(
+                        AbilityError::PermissionRequestTimeout(timeout).to_string(),
+                    )));
+                }
+            }
+            pending = pending_permission_requests();
+            continue;
+        }
+
+        let next_deadline = pending
+            .values()
+            .filter_map(|request| request.deadline.map(|(deadline, _)| deadline))
+            .min();
+        pending = match next_deadline {
+            Some(deadline) => {
+                PERMISSION_TIMEOUT_SIGNAL
+                    .wait_timeout(pending, deadline.saturating_duration_since(now))
+                    .expect("Failed to lock PENDING_PERMISSION_REQUESTS")
+                    .0
+            }
+            None => PERMISSION_TIMEOUT_SIGNAL
+                .wait(pending)
+                .expect("Failed to lock PENDING_PERMISSION_REQUESTS"),
+        };
+    }
+}
+
+/// Removes the request from the registry when the future is dropped before it resolves
+struct PendingPermissionRequest(u64);
+
+impl PendingPermissionRequest {
+    fn register(method: &str, sender: PermissionResultSender) -> Self {
+        let request_id = next_permission_request_id();
+        let deadline =
+            permission_request_timeout().map(|timeout| (Instant::now() + timeout, timeout));
+        pending_permission_requests().insert(
+            request_id,
+            PendingRequest {
+                method: method.to_string(),
+                sender,
+                deadline,
+            },
+        );
+        if deadline.is_some() {
+            PERMISSION_TIMEOUT_THREAD.call_once(|| {
+                if let Err(err) = std::thread::Builder::new()
+                    .name("permission-timeout".to_string())
+                    .spawn(run_permission_timeouts)
+                {
+                    log_error!("Failed to spawn the permission timeout thread: {}", err);
+                }
+            });
+            PERMISSION_TIMEOUT_SIGNAL.notify_one();
+        }
+        Self(request_id)
+    }
+}
+
+impl Drop for PendingPermissionRequest {
+    fn drop(&mut self) {
+        pending_permission_requests().remove(&self.0);
+    }
+}
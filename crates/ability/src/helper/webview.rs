@@ -13,7 +13,7 @@ use napi_ohos::{
 };
 use ohos_web_binding::{ArkWebResponse, CustomProtocolHandler, Web};
 
-use crate::get_main_thread_env;
+use crate::{catch_panic, get_main_thread_env};
 
 #[napi(object)]
 #[derive(Debug, Clone, Default)]
@@ -205,15 +205,17 @@ impl Webview {
                 )?;
 
             let cb = env.create_function_from_closure("evaluate_js_callback", move |ctx| {
-                let ret = ctx.try_get::<String>(1)?;
-                let ret = match ret {
-                    Either::A(s) => s,
-                    Either::B(_ret) => String::from("undefined"),
-                };
-                if let Some(cb) = callback.as_ref() {
-                    cb(ret);
-                }
-                Ok(())
+                catch_panic("evaluate_js_callback", || {
+                    let ret = ctx.try_get::<String>(1)?;
+                    let ret = match ret {
+                        Either::A(s) => s,
+                        Either::B(_ret) => String::from("undefined"),
+                    };
+                    if let Some(cb) = callback.as_ref() {
+                        cb(ret);
+                    }
+                    Ok(())
+                })
             })?;
 
             evaluate_js_js_function.call((js.to_string(), cb).into())?;
@@ -0,0 +1,95 @@
+use std::{cell::RefCell, rc::Rc};
+
+/// Returned by `OpenHarmonyApp::push_back_handler`, used to remove the handler again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BackHandlerId(u64);
+
+/// Screen edge a back swipe started from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackEdge {
+    Left,
+    Right,
+    Unknown,
+}
+
+impl From<i32> for BackEdge {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => BackEdge::Left,
+            1 => BackEdge::Right,
+            _ => BackEdge::Unknown,
+        }
+    }
+}
+
+/// Predictive back, the back swipe before it is committed.
+/// `Committed` is followed by `Event::BackPressed` and the back handlers, like a back press.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackGestureEvent {
+    Started {
+        edge: BackEdge,
+    },
+    /// `progress` goes from 0.0 to 1.0
+    Progressed {
+        progress: f32,
+        edge: BackEdge,
+    },
+    Cancelled,
+    Committed,
+}
+
+type BackHandler = Rc<RefCell<Box<dyn FnMut() -> bool + 'static>>>;
+
+/// Back handlers in push order, the last pushed handler is asked first
+#[derive(Default)]
+pub(crate) struct BackHandlers {
+    next_id: u64,
+    handlers: Vec<(BackHandlerId, BackHandler)>,
+    /// handler registered with `on_back_press_intercept`, replaced on every call
+    interceptor: Option<BackHandlerId>,
+}
+
+impl BackHandlers {
+    pub(crate) fn push(&mut self, handler: Box<dyn FnMut() -> bool + 'static>) -> BackHandlerId {
+        let id = BackHandlerId(self.next_id);
+        self.next_id += 1;
+        self.handlers.push((id, Rc::new(RefCell::new(handler))));
+        id
+    }
+
+    pub(crate) fn replace_interceptor(&mut self, handler: Box<dyn FnMut() -> bool + 'static>) {
+        if let Some(id) = self.interceptor.take() {
+            self.remove(id);
+        }
+        let id = BackHandlerId(self.next_id);
+        self.next_id += 1;
+        // at the bottom, so pushed handlers are always asked first
+        self.handlers
+            .insert(0, (id, Rc::new(RefCell::new(handler))));
+        self.interceptor = Some(id);
+    }
+
+    /// Remove the last pushed handler, the interceptor is only replaced or removed by id
+    pub(crate) fn pop(&mut self) -> Option<BackHandlerId> {
+        let index = self
+            .handlers
+            .iter()
+            .rposition(|(id, _)| Some(*id) != self.interceptor)?;
+        Some(self.handlers.remove(index).0)
+    }
+
+    pub(crate) fn remove(&mut self, id: BackHandlerId) -> bool {
+        let len = self.handlers.len();
+        self.handlers.retain(|(handler_id, _)| *handler_id != id);
+        self.handlers.len() != len
+    }
+
+    /// Handlers from top to bottom, cloned so a handler may push or remove handlers while it runs
+    pub(crate) fn snapshot(&self) -> Vec<BackHandler> {
+        self.handlers
+            .iter()
+            .rev()
+            .map(|(_, handler)| handler.clone())
+            .collect()
+    }
+}
@@ -1,12 +1,487 @@
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    ptr::{self, NonNull},
+    sync::{Arc, LazyLock, Mutex, RwLock},
+};
+
 use napi_ohos::{
-    bindgen_prelude::Function, threadsafe_function::ThreadsafeFunction, Env, Result, Status,
+    bindgen_prelude::Function,
+    threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+    Env, Result, Status,
+};
+use ohos_ime_binding::{Direction, EnterKey, InputType, KeyboardStatus, Selection};
+use ohos_input_method_sys::{
+    InputMethod_AttachOptions, InputMethod_Direction, InputMethod_EnterKeyType,
+    InputMethod_ExtendAction, InputMethod_InputMethodProxy, InputMethod_KeyboardStatus,
+    InputMethod_PrivateCommand, InputMethod_TextConfig, InputMethod_TextEditorProxy,
+    OH_AttachOptions_Create, OH_AttachOptions_Destroy, OH_CursorInfo_Create, OH_CursorInfo_Destroy,
+    OH_CursorInfo_SetRect, OH_InputMethodController_Attach, OH_InputMethodController_Detach,
+    OH_InputMethodProxy_HideKeyboard, OH_InputMethodProxy_NotifyConfigurationChange,
+    OH_InputMethodProxy_NotifyCursorUpdate, OH_InputMethodProxy_NotifySelectionChange,
+    OH_InputMethodProxy_ShowKeyboard, OH_TextConfig_GetCursorInfo, OH_TextConfig_SetEnterKeyType,
+    OH_TextConfig_SetInputType, OH_TextConfig_SetPreviewTextSupport, OH_TextConfig_SetSelection,
+    OH_TextEditorProxy_Create, OH_TextEditorProxy_Destroy,
+    OH_TextEditorProxy_SetDeleteBackwardFunc, OH_TextEditorProxy_SetDeleteForwardFunc,
+    OH_TextEditorProxy_SetFinishTextPreviewFunc, OH_TextEditorProxy_SetGetLeftTextOfCursorFunc,
+    OH_TextEditorProxy_SetGetRightTextOfCursorFunc, OH_TextEditorProxy_SetGetTextConfigFunc,
+    OH_TextEditorProxy_SetGetTextIndexAtCursorFunc, OH_TextEditorProxy_SetHandleExtendActionFunc,
+    OH_TextEditorProxy_SetHandleSetSelectionFunc, OH_TextEditorProxy_SetInsertTextFunc,
+    OH_TextEditorProxy_SetMoveCursorFunc, OH_TextEditorProxy_SetReceivePrivateCommandFunc,
+    OH_TextEditorProxy_SetSendEnterKeyFunc, OH_TextEditorProxy_SetSendKeyboardStatusFunc,
+    OH_TextEditorProxy_SetSetPreviewTextFunc,
 };
-use ohos_ime_binding::KeyboardStatus;
 
-use crate::{Event, OpenHarmonyApp};
+use crate::{catch_panic, Event, OpenHarmonyApp, Rect};
 
 use super::{ImeEvent, InputEvent, TextInputEventData};
 
+type ImeThreadsafeFunction = ThreadsafeFunction<(), ()>;
+
+/// IME callbacks may run on the IME thread, events are queued and drained on the main thread
+static IME_EVENTS: LazyLock<Mutex<VecDeque<ImeEvent>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+static IME_TSFN: LazyLock<RwLock<Option<Arc<ImeThreadsafeFunction>>>> =
+    LazyLock::new(|| RwLock::new(None));
+/// Current preview text, replaced by the next `insert_text` or cleared by `finish_text_preview`
+static IME_PREVIEW: LazyLock<Mutex<String>> = LazyLock::new(|| Mutex::new(String::new()));
+/// Text input state queried by the IME thread
+static IME_STATE: LazyLock<Mutex<ImeState>> = LazyLock::new(|| Mutex::new(ImeState::default()));
+
+/// Keyboard layout and behavior for the text field being edited.
+/// OpenHarmony has no autocorrect switch, the input method decides on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImeOptions {
+    pub input_type: InputType,
+    pub enter_key_type: EnterKey,
+    /// show the composing text with `ImeEvent::Preedit` instead of inserting it directly
+    pub preview_text: bool,
+}
+
+impl Default for ImeOptions {
+    fn default() -> Self {
+        Self {
+            input_type: InputType::Text,
+            enter_key_type: EnterKey::Unspecified,
+            preview_text: true,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ImeState {
+    options: ImeOptions,
+    /// caret rect in screen px
+    cursor_area: Option<Rect>,
+    /// surrounding text in UTF-16, the IME counts in UTF-16 code units
+    text: Vec<u16>,
+    selection: (usize, usize),
+}
+
+fn ime_state() -> std::sync::MutexGuard<'static, ImeState> {
+    IME_STATE.lock().expect("Failed to lock IME_STATE")
+}
+
+/// Convert a byte offset of `text` to UTF-16 code units
+fn utf16_offset(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    text[..offset].encode_utf16().count()
+}
+
+/// Copy `src` into the buffer provided by the IME, `len` holds the capacity and receives the length
+unsafe fn write_utf16(src: &[u16], text: *mut u16, len: *mut usize) {
+    if len.is_null() {
+        return;
+    }
+    if text.is_null() {
+        *len = 0;
+        return;
+    }
+    let count = src.len().min(*len);
+    ptr::copy_nonoverlapping(src.as_ptr(), text, count);
+    *len = count;
+}
+
+fn push_ime_event(event: ImeEvent) {
+    IME_EVENTS
+        .lock()
+        .expect("Failed to lock IME_EVENTS")
+        .push_back(event);
+    if let Some(tsfn) = IME_TSFN.read().expect("Failed to read IME_TSFN").as_ref() {
+        tsfn.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+}
+
+/// Create the threadsafe function which delivers queued IME events to the event loop
+pub(crate) fn create_ime_tsfn(env: &Env, app: OpenHarmonyApp) -> Result<()> {
+    let ime_event_callback: Function<'_, (), ()> =
+        env.create_function_from_closure("ime_event_callback", move |_ctx| {
+            catch_panic("ime_event_callback", || {
+                loop {
+                    let event = IME_EVENTS
+                        .lock()
+                        .expect("Failed to lock IME_EVENTS")
+                        .pop_front();
+                    let Some(event) = event else {
+                        break;
+                    };
+                    match event {
+                        ImeEvent::ImeStatusEvent(KeyboardStatus::Hide) => {
+                            app.set_keyboard_visibility(false, None);
+                        }
+                        ImeEvent::ImeStatusEvent(KeyboardStatus::Show) => {
+                            app.set_keyboard_visibility(true, None);
+                        }
+                        _ => {}
+                    }
+                    if let Some(ref mut h) = *app.event_loop.borrow_mut() {
+                        h(Event::Input(InputEvent::ImeEvent(event)))
+                    }
+                }
+                Ok(())
+            })
+        })?;
+
+    let tsfn = ime_event_callback
+        .build_threadsafe_function()
+        .callee_handled::<true>()
+        .build()?;
+
+    IME_TSFN
+        .write()
+        .map_err(|_| napi_ohos::Error::from_reason("Failed to write IME_TSFN"))?
+        .replace(Arc::new(tsfn));
+    Ok(())
+}
+
+fn utf16_to_string(text: *const u16, len: usize) -> String {
+    if text.is_null() || len == 0 {
+        return String::new();
+    }
+    let slice = unsafe { std::slice::from_raw_parts(text, len) };
+    String::from_utf16_lossy(slice)
+}
+
+unsafe extern "C" fn get_text_config(
+    _editor: *mut InputMethod_TextEditorProxy,
+    config: *mut InputMethod_TextConfig,
+) {
+    let state = ime_state();
+    OH_TextConfig_SetInputType(config, state.options.input_type.into());
+    OH_TextConfig_SetEnterKeyType(config, state.options.enter_key_type.into());
+    OH_TextConfig_SetPreviewTextSupport(config, state.options.preview_text);
+    OH_TextConfig_SetSelection(config, state.selection.0 as _, state.selection.1 as _);
+    if let Some(area) = state.cursor_area {
+        let mut cursor = ptr::null_mut();
+        OH_TextConfig_GetCursorInfo(config, &mut cursor);
+        if !cursor.is_null() {
+            OH_CursorInfo_SetRect(
+                cursor,
+                area.left as _,
+                area.top as _,
+                area.width as _,
+                area.height as _,
+            );
+        }
+    }
+}
+
+unsafe extern "C" fn insert_text(
+    _editor: *mut InputMethod_TextEditorProxy,
+    text: *const u16,
+    len: usize,
+) {
+    let text = utf16_to_string(text, len);
+    let preview = std::mem::take(&mut *IME_PREVIEW.lock().expect("Failed to lock IME_PREVIEW"));
+    if preview.is_empty() {
+        push_ime_event(ImeEvent::TextInputEvent(TextInputEventData { text }));
+    } else {
+        push_ime_event(ImeEvent::Commit(text));
+    }
+}
+
+unsafe extern "C" fn delete_backward(_editor: *mut InputMethod_TextEditorProxy, len: i32) {
+    push_ime_event(ImeEvent::BackspaceEvent(len));
+}
+
+unsafe extern "C" fn delete_forward(_editor: *mut InputMethod_TextEditorProxy, len: i32) {
+    push_ime_event(ImeEvent::DeleteForward(len));
+}
+
+unsafe extern "C" fn send_keyboard_status(
+    _editor: *mut InputMethod_TextEditorProxy,
+    status: InputMethod_KeyboardStatus,
+) {
+    push_ime_event(ImeEvent::ImeStatusEvent(KeyboardStatus::from(status)));
+}
+
+unsafe extern "C" fn send_enter_key(
+    _editor: *mut InputMethod_TextEditorProxy,
+    key: InputMethod_EnterKeyType,
+) {
+    push_ime_event(ImeEvent::EnterEvent(EnterKey::from(key) as i32));
+}
+
+unsafe extern "C" fn move_cursor(
+    _editor: *mut InputMethod_TextEditorProxy,
+    direction: InputMethod_Direction,
+) {
+    push_ime_event(ImeEvent::MoveCursor(Direction::from(direction)));
+}
+
+unsafe extern "C" fn handle_set_selection(
+    _editor: *mut InputMethod_TextEditorProxy,
+    start: i32,
+    end: i32,
+) {
+    push_ime_event(ImeEvent::SetSelection(Selection { start, end }));
+}
+
+unsafe extern "C" fn handle_extend_action(
+    _editor: *mut InputMethod_TextEditorProxy,
+    _action: InputMethod_ExtendAction,
+) {
+}
+
+unsafe extern "C" fn get_left_text_of_cursor(
+    _editor: *mut InputMethod_TextEditorProxy,
+    number: i32,
+    text: *mut u16,
+    len: *mut usize,
+) {
+    let state = ime_state();
+    let end = state.selection.0;
+    let start = end.saturating_sub(number.max(0) as usize);
+    write_utf16(&state.text[start..end], text, len);
+}
+
+unsafe extern "C" fn get_right_text_of_cursor(
+    _editor: *mut InputMethod_TextEditorProxy,
+    number: i32,
+    text: *mut u16,
+    len: *mut usize,
+) {
+    let state = ime_state();
+    let start = state.selection.1;
+    let end = (start + number.max(0) as usize).min(state.text.len());
+    write_utf16(&state.text[start..end], text, len);
+}
+
+unsafe extern "C" fn get_text_index_at_cursor(_editor: *mut InputMethod_TextEditorProxy) -> i32 {
+    ime_state().selection.0 as i32
+}
+
+unsafe extern "C" fn receive_private_command(
+    _editor: *mut InputMethod_TextEditorProxy,
+    _command: *mut *mut InputMethod_PrivateCommand,
+    _len: usize,
+) -> i32 {
+    0
+}
+
+unsafe extern "C" fn set_preview_text(
+    _editor: *mut InputMethod_TextEditorProxy,
+    text: *const u16,
+    len: usize,
+    start: i32,
+    end: i32,
+) -> i32 {
+    let text = utf16_to_string(text, len);
+    *IME_PREVIEW.lock().expect("Failed to lock IME_PREVIEW") = text.clone();
+
+    let cursor_range = (!text.is_empty()).then_some((text.len(), text.len()));
+    let replace_range = (start >= 0 && end >= start).then_some((start, end));
+    push_ime_event(ImeEvent::Preedit {
+        text,
+        cursor_range,
+        replace_range,
+    });
+    0
+}
+
+/// Only clears the preedit, the IME inserts the final text with `insert_text`
+unsafe extern "C" fn finish_text_preview(_editor: *mut InputMethod_TextEditorProxy) {
+    let preview = std::mem::take(&mut *IME_PREVIEW.lock().expect("Failed to lock IME_PREVIEW"));
+    if !preview.is_empty() {
+        push_ime_event(ImeEvent::Preedit {
+            text: String::new(),
+            cursor_range: None,
+            replace_range: None,
+        });
+    }
+}
+
+pub(crate) fn ime_options() -> ImeOptions {
+    ime_state().options
+}
+
+/// Store the options for the next attach and notify the attached IME
+pub(crate) fn set_ime_options(ime: Option<&Ime>, options: ImeOptions) {
+    ime_state().options = options;
+    if let Some(ime) = ime {
+        ime.notify_options(options);
+    }
+}
+
+/// `area` is in screen px
+pub(crate) fn set_ime_cursor_area(ime: Option<&Ime>, area: Rect) {
+    ime_state().cursor_area = Some(area);
+    if let Some(ime) = ime {
+        ime.notify_cursor_area(area);
+    }
+}
+
+/// `selection` is a byte range of `text`
+pub(crate) fn set_ime_surrounding_text(ime: Option<&Ime>, text: &str, selection: Range<usize>) {
+    let start = utf16_offset(text, selection.start);
+    let end = utf16_offset(text, selection.end).max(start);
+    let mut utf16: Vec<u16> = text.encode_utf16().collect();
+    if let Some(ime) = ime {
+        ime.notify_selection(&mut utf16, start, end);
+    }
+    let mut state = ime_state();
+    state.text = utf16;
+    state.selection = (start, end);
+}
+
+/// Text editor proxy attached to the system input method.
+/// All callbacks are registered here, so preview text, selection and cursor requests are delivered.
+pub(crate) struct Ime {
+    editor: *mut InputMethod_TextEditorProxy,
+    options: *mut InputMethod_AttachOptions,
+    proxy: Option<NonNull<InputMethod_InputMethodProxy>>,
+}
+
+impl Ime {
+    pub(crate) fn new() -> Self {
+        unsafe {
+            let editor = OH_TextEditorProxy_Create();
+            OH_TextEditorProxy_SetGetTextConfigFunc(editor, Some(get_text_config));
+            OH_TextEditorProxy_SetInsertTextFunc(editor, Some(insert_text));
+            OH_TextEditorProxy_SetDeleteBackwardFunc(editor, Some(delete_backward));
+            OH_TextEditorProxy_SetDeleteForwardFunc(editor, Some(delete_forward));
+            OH_TextEditorProxy_SetSendKeyboardStatusFunc(editor, Some(send_keyboard_status));
+            OH_TextEditorProxy_SetSendEnterKeyFunc(editor, Some(send_enter_key));
+            OH_TextEditorProxy_SetMoveCursorFunc(editor, Some(move_cursor));
+            OH_TextEditorProxy_SetHandleSetSelectionFunc(editor, Some(handle_set_selection));
+            OH_TextEditorProxy_SetHandleExtendActionFunc(editor, Some(handle_extend_action));
+            OH_TextEditorProxy_SetGetLeftTextOfCursorFunc(editor, Some(get_left_text_of_cursor));
+            OH_TextEditorProxy_SetGetRightTextOfCursorFunc(editor, Some(get_right_text_of_cursor));
+            OH_TextEditorProxy_SetGetTextIndexAtCursorFunc(editor, Some(get_text_index_at_cursor));
+            OH_TextEditorProxy_SetReceivePrivateCommandFunc(editor, Some(receive_private_command));
+            OH_TextEditorProxy_SetSetPreviewTextFunc(editor, Some(set_preview_text));
+            OH_TextEditorProxy_SetFinishTextPreviewFunc(editor, Some(finish_text_preview));
+
+            Self {
+                editor,
+                options: OH_AttachOptions_Create(true),
+                proxy: None,
+            }
+        }
+    }
+
+    fn attach(&mut self) {
+        if self.proxy.is_some() {
+            return;
+        }
+        let mut raw: *mut InputMethod_InputMethodProxy = ptr::null_mut();
+        let ret = unsafe { OH_InputMethodController_Attach(self.editor, self.options, &mut raw) };
+        if ret == 0 {
+            self.proxy = NonNull::new(raw);
+        }
+    }
+
+    pub(crate) fn show_keyboard(&mut self) {
+        self.attach();
+        if let Some(proxy) = self.proxy {
+            unsafe { OH_InputMethodProxy_ShowKeyboard(proxy.as_ptr()) };
+        }
+    }
+
+    /// Only hides the keyboard, the editor stays attached so commits from the IME keep arriving
+    pub(crate) fn hide_keyboard(&self) {
+        if let Some(proxy) = self.proxy {
+            unsafe { OH_InputMethodProxy_HideKeyboard(proxy.as_ptr()) };
+        }
+    }
+
+    fn notify_options(&self, options: ImeOptions) {
+        if let Some(proxy) = self.proxy {
+            unsafe {
+                OH_InputMethodProxy_NotifyConfigurationChange(
+                    proxy.as_ptr(),
+                    options.enter_key_type.into(),
+                    options.input_type.into(),
+                )
+            };
+        }
+    }
+
+    fn notify_cursor_area(&self, area: Rect) {
+        if let Some(proxy) = self.proxy {
+            unsafe {
+                let cursor = OH_CursorInfo_Create(
+                    area.left as _,
+                    area.top as _,
+                    area.width as _,
+                    area.height as _,
+                );
+                OH_InputMethodProxy_NotifyCursorUpdate(proxy.as_ptr(), cursor);
+                OH_CursorInfo_Destroy(cursor);
+            }
+        }
+    }
+
+    fn notify_selection(&self, text: &mut [u16], start: usize, end: usize) {
+        if let Some(proxy) = self.proxy {
+            unsafe {
+                OH_InputMethodProxy_NotifySelectionChange(
+                    proxy.as_ptr(),
+                    text.as_mut_ptr(),
+                    text.len(),
+                    start as _,
+                    end as _,
+                )
+            };
+        }
+    }
+
+    fn detach(&mut self) {
+        if let Some(proxy) = self.proxy.take() {
+            unsafe { OH_InputMethodController_Detach(proxy.as_ptr()) };
+        }
+        IME_PREVIEW
+            .lock()
+            .expect("Failed to lock IME_PREVIEW")
+            .clear();
+    }
+}
+
+impl Drop for Ime {
+    fn drop(&mut self) {
+        self.detach();
+        // events and text of this editor must not reach the next one,
+        // a queued keyboard status is still delivered so the visibility stays right
+        IME_EVENTS
+            .lock()
+            .expect("Failed to lock IME_EVENTS")
+            .retain(|event| matches!(event, ImeEvent::ImeStatusEvent(_)));
+        {
+            let mut state = ime_state();
+            state.cursor_area = None;
+            state.text.clear();
+            state.selection = (0, 0);
+        }
+        unsafe {
+            OH_TextEditorProxy_Destroy(self.editor);
+            OH_AttachOptions_Destroy(self.options);
+        }
+    }
+}
+
 type ImeCallback = (
     ThreadsafeFunction<String, (), String, Status, false>,
     ThreadsafeFunction<u32, (), u32, Status, false>,
@@ -14,87 +489,70 @@ type ImeCallback = (
     ThreadsafeFunction<i32, (), i32, Status, false>,
 );
 
+/// Create the old per-event IME threadsafe functions.
+/// `render` delivers all IME events itself now, this is kept for code calling it directly.
+#[deprecated(note = "IME events are delivered by `render`, use `OpenHarmonyApp::start_text_input`")]
 pub fn ime_ts_fn(env: &Env, app: OpenHarmonyApp) -> Result<ImeCallback> {
-    // insert event
-    let on_insert_text_app = app.clone();
+    let dispatch = move |event: ImeEvent| {
+        if let Some(ref mut h) = *app.event_loop.borrow_mut() {
+            h(Event::Input(InputEvent::ImeEvent(event)))
+        }
+    };
+
+    let on_insert_text = dispatch.clone();
     let insert_text_callback: Function<String, ()> =
         env.create_function_from_closure("ime_insert_callback", move |ctx| {
-            let s = ctx.first_arg::<String>().unwrap();
-            if let Some(ref mut h) = *on_insert_text_app.event_loop.borrow_mut() {
-                h(Event::Input(InputEvent::ImeEvent(
-                    ImeEvent::TextInputEvent(TextInputEventData { text: s }),
-                )))
-            }
-            Ok(())
+            catch_panic("ime_insert_callback", || {
+                let text = ctx.first_arg::<String>()?;
+                on_insert_text(ImeEvent::TextInputEvent(TextInputEventData { text }));
+                Ok(())
+            })
         })?;
 
-    let insert_text_callback_tsfn = insert_text_callback
-        .build_threadsafe_function()
-        .callee_handled::<false>()
-        .build()?;
-
-    // keyboard status event
-    let on_ime_hide_app = app.clone();
+    let on_status = dispatch.clone();
     let on_ime_hide_callback: Function<u32, ()> =
         env.create_function_from_closure("ime_hide_callback", move |ctx| {
-            let value = ctx.first_arg::<u32>().unwrap();
-
-            let status = KeyboardStatus::from(value);
-            if matches!(status, KeyboardStatus::Hide) {
-                // Keep native IME lifecycle aligned with hide callbacks.
-                on_ime_hide_app.hide_keyboard();
-            }
-            if let Some(ref mut h) = *on_ime_hide_app.event_loop.borrow_mut() {
-                h(Event::Input(InputEvent::ImeEvent(
-                    ImeEvent::ImeStatusEvent(status),
-                )))
-            }
-            Ok(())
+            catch_panic("ime_hide_callback", || {
+                let status = KeyboardStatus::from(ctx.first_arg::<u32>()?);
+                on_status(ImeEvent::ImeStatusEvent(status));
+                Ok(())
+            })
         })?;
 
-    let on_ime_hide_callback_tsfn = on_ime_hide_callback
-        .build_threadsafe_function()
-        .callee_handled::<false>()
-        .build()?;
-
-    let on_backspace_app = app.clone();
+    let on_backspace = dispatch.clone();
     let on_backspace_callback: Function<i32, ()> =
         env.create_function_from_closure("on_backspace_callback", move |ctx| {
-            let value = ctx.first_arg::<i32>().unwrap();
-            if let Some(ref mut h) = *on_backspace_app.event_loop.borrow_mut() {
-                h(Event::Input(InputEvent::ImeEvent(
-                    ImeEvent::BackspaceEvent(value),
-                )))
-            }
-            Ok(())
+            catch_panic("on_backspace_callback", || {
+                on_backspace(ImeEvent::BackspaceEvent(ctx.first_arg::<i32>()?));
+                Ok(())
+            })
         })?;
 
-    let on_backspace_callback_tsfn = on_backspace_callback
-        .build_threadsafe_function()
-        .callee_handled::<false>()
-        .build()?;
-
-    let on_ime_enter_app = app.clone();
+    let on_enter = dispatch;
     let on_ime_enter_callback: Function<i32, ()> =
         env.create_function_from_closure("on_ime_enter_callback", move |ctx| {
-            let value = ctx.first_arg::<i32>().unwrap();
-            if let Some(ref mut h) = *on_ime_enter_app.event_loop.borrow_mut() {
-                h(Event::Input(InputEvent::ImeEvent(ImeEvent::EnterEvent(
-                    value,
-                ))))
-            }
-            Ok(())
+            catch_panic("on_ime_enter_callback", || {
+                on_enter(ImeEvent::EnterEvent(ctx.first_arg::<i32>()?));
+                Ok(())
+            })
         })?;
 
-    let on_ime_enter_callback_tsfn = on_ime_enter_callback
-        .build_threadsafe_function()
-        .callee_handled::<false>()
-        .build()?;
-
     Ok((
-        insert_text_callback_tsfn,
-        on_ime_hide_callback_tsfn,
-        on_backspace_callback_tsfn,
-        on_ime_enter_callback_tsfn,
+        insert_text_callback
+            .build_threadsafe_function()
+            .callee_handled::<false>()
+            .build()?,
+        on_ime_hide_callback
+            .build_threadsafe_function()
+            .callee_handled::<false>()
+            .build()?,
+        on_backspace_callback
+            .build_threadsafe_function()
+            .callee_handled::<false>()
+            .build()?,
+        on_ime_enter_callback
+            .build_threadsafe_function()
+            .callee_handled::<false>()
+            .build()?,
     ))
 }
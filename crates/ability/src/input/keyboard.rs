@@ -0,0 +1,282 @@
+use ohos_xcomponent_binding::{Action, KeyCode, KeyEventData};
+
+/// Logical key, i.e. the meaning of the key with the current modifiers applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// printable key, including space
+    Character(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Escape,
+    Insert,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Shift,
+    Control,
+    Alt,
+    Meta,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    /// function key `F1` to `F24`
+    F(u8),
+    /// system back key
+    Back,
+    Menu,
+    /// use `KeyboardEvent::code` for keys without a logical mapping
+    Unidentified,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+/// Modifier state after the event has been applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub meta: bool,
+    pub caps_lock: bool,
+}
+
+impl Modifiers {
+    /// Whether a shortcut modifier is held, i.e. ctrl, alt or meta
+    pub fn has_shortcut(&self) -> bool {
+        self.ctrl || self.alt || self.meta
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardEvent {
+    pub key: Key,
+    /// physical key
+    pub code: KeyCode,
+    pub state: KeyState,
+    pub modifiers: Modifiers,
+    /// `true` when the key is held and the system sends the press again
+    pub repeat: bool,
+    /// character produced by the press, `None` for releases and shortcuts
+    pub text: Option<char>,
+    pub timestamp: i64,
+}
+
+/// Track held keys and modifiers to build `KeyboardEvent` from `KeyEventData`
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KeyboardState {
+    modifiers: Modifiers,
+    pressed: Vec<KeyCode>,
+}
+
+impl KeyboardState {
+    pub(crate) fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Release all keys, e.g. when the window loses focus and the releases never arrive.
+    /// Caps lock is a toggle and is kept.
+    pub(crate) fn reset(&mut self) {
+        self.pressed.clear();
+        self.modifiers = Modifiers {
+            caps_lock: self.modifiers.caps_lock,
+            ..Default::default()
+        };
+    }
+
+    pub(crate) fn process(&mut self, data: &KeyEventData) -> Option<KeyboardEvent> {
+        let state = match data.action {
+            Action::Down => KeyState::Pressed,
+            Action::Up => KeyState::Released,
+            _ => return None,
+        };
+        let code = data.code;
+        let repeat = match state {
+            KeyState::Pressed => {
+                let repeat = self.pressed.contains(&code);
+                if !repeat {
+                    self.pressed.push(code);
+                }
+                repeat
+            }
+            KeyState::Released => {
+                self.pressed.retain(|c| *c != code);
+                false
+            }
+        };
+
+        let held = |codes: [KeyCode; 2]| codes.iter().any(|c| self.pressed.contains(c));
+        let mut modifiers = Modifiers {
+            shift: held([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            ctrl: held([KeyCode::CtrlLeft, KeyCode::CtrlRight]),
+            alt: held([KeyCode::AltLeft, KeyCode::AltRight]),
+            meta: held([KeyCode::MetaLeft, KeyCode::MetaRight]),
+            caps_lock: self.modifiers.caps_lock,
+        };
+        if code == KeyCode::CapsLock && state == KeyState::Pressed && !repeat {
+            modifiers.caps_lock = !modifiers.caps_lock;
+        }
+        self.modifiers = modifiers;
+
+        let key = logical_key(code, modifiers);
+        let text = match (state, key) {
+            (KeyState::Pressed, Key::Character(c)) if !modifiers.has_shortcut() => Some(c),
+            _ => None,
+        };
+
+        Some(KeyboardEvent {
+            key,
+            code,
+            state,
+            modifiers,
+            repeat,
+            text,
+            timestamp: data.timestamp,
+        })
+    }
+}
+
+/// Map a physical key to a logical key with a US layout
+fn logical_key(code: KeyCode, modifiers: Modifiers) -> Key {
+    let shift = modifiers.shift;
+    let letter = |c: char| {
+        if shift != modifiers.caps_lock {
+            Key::Character(c.to_ascii_uppercase())
+        } else {
+            Key::Character(c)
+        }
+    };
+    let symbol = |plain: char, shifted: char| Key::Character(if shift { shifted } else { plain });
+
+    match code {
+        KeyCode::A => letter('a'),
+        KeyCode::B => letter('b'),
+        KeyCode::C => letter('c'),
+        KeyCode::D => letter('d'),
+        KeyCode::E => letter('e'),
+        KeyCode::F => letter('f'),
+        KeyCode::G => letter('g'),
+        KeyCode::H => letter('h'),
+        KeyCode::I => letter('i'),
+        KeyCode::J => letter('j'),
+        KeyCode::K => letter('k'),
+        KeyCode::L => letter('l'),
+        KeyCode::M => letter('m'),
+        KeyCode::N => letter('n'),
+        KeyCode::O => letter('o'),
+        KeyCode::P => letter('p'),
+        KeyCode::Q => letter('q'),
+        KeyCode::R => letter('r'),
+        KeyCode::S => letter('s'),
+        KeyCode::T => letter('t'),
+        KeyCode::U => letter('u'),
+        KeyCode::V => letter('v'),
+        KeyCode::W => letter('w'),
+        KeyCode::X => letter('x'),
+        KeyCode::Y => letter('y'),
+        KeyCode::Z => letter('z'),
+        KeyCode::Key0 => symbol('0', ')'),
+        KeyCode::Key1 => symbol('1', '!'),
+        KeyCode::Key2 => symbol('2', '@'),
+        KeyCode::Key3 => symbol('3', '#'),
+        KeyCode::Key4 => symbol('4', '$'),
+        KeyCode::Key5 => symbol('5', '%'),
+        KeyCode::Key6 => symbol('6', '^'),
+        KeyCode::Key7 => symbol('7', '&'),
+        KeyCode::Key8 => symbol('8', '*'),
+        KeyCode::Key9 => symbol('9', '('),
+        KeyCode::Grave => symbol('`', '~'),
+        KeyCode::Minus => symbol('-', '_'),
+        KeyCode::Equals => symbol('=', '+'),
+        KeyCode::LeftBracket => symbol('[', '{'),
+        KeyCode::RightBracket => symbol(']', '}'),
+        KeyCode::Backslash => symbol('\\', '|'),
+        KeyCode::Semicolon => symbol(';', ':'),
+        KeyCode::Apostrophe => symbol('\'', '"'),
+        KeyCode::Comma => symbol(',', '<'),
+        KeyCode::Period => symbol('.', '>'),
+        KeyCode::Slash => symbol('/', '?'),
+        KeyCode::Star => Key::Character('*'),
+        KeyCode::Pound => Key::Character('#'),
+        KeyCode::At => Key::Character('@'),
+        KeyCode::Plus => Key::Character('+'),
+        KeyCode::Space => Key::Character(' '),
+        KeyCode::Numpad0 => Key::Character('0'),
+        KeyCode::Numpad1 => Key::Character('1'),
+        KeyCode::Numpad2 => Key::Character('2'),
+        KeyCode::Numpad3 => Key::Character('3'),
+        KeyCode::Numpad4 => Key::Character('4'),
+        KeyCode::Numpad5 => Key::Character('5'),
+        KeyCode::Numpad6 => Key::Character('6'),
+        KeyCode::Numpad7 => Key::Character('7'),
+        KeyCode::Numpad8 => Key::Character('8'),
+        KeyCode::Numpad9 => Key::Character('9'),
+        KeyCode::NumpadDivide => Key::Character('/'),
+        KeyCode::NumpadMultiply => Key::Character('*'),
+        KeyCode::NumpadSubtract => Key::Character('-'),
+        KeyCode::NumpadAdd => Key::Character('+'),
+        KeyCode::NumpadDot => Key::Character('.'),
+        KeyCode::NumpadComma => Key::Character(','),
+        KeyCode::NumpadEquals => Key::Character('='),
+        KeyCode::NumpadLeftParen => Key::Character('('),
+        KeyCode::NumpadRightParen => Key::Character(')'),
+        KeyCode::Enter | KeyCode::NumpadEnter => Key::Enter,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Del => Key::Backspace,
+        KeyCode::ForwardDel => Key::Delete,
+        KeyCode::Escape => Key::Escape,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::DpadUp => Key::ArrowUp,
+        KeyCode::DpadDown => Key::ArrowDown,
+        KeyCode::DpadLeft => Key::ArrowLeft,
+        KeyCode::DpadRight => Key::ArrowRight,
+        KeyCode::MoveHome => Key::Home,
+        KeyCode::MoveEnd => Key::End,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => Key::Shift,
+        KeyCode::CtrlLeft | KeyCode::CtrlRight => Key::Control,
+        KeyCode::AltLeft | KeyCode::AltRight => Key::Alt,
+        KeyCode::MetaLeft | KeyCode::MetaRight => Key::Meta,
+        KeyCode::CapsLock => Key::CapsLock,
+        KeyCode::NumLock => Key::NumLock,
+        KeyCode::ScrollLock => Key::ScrollLock,
+        KeyCode::F1 => Key::F(1),
+        KeyCode::F2 => Key::F(2),
+        KeyCode::F3 => Key::F(3),
+        KeyCode::F4 => Key::F(4),
+        KeyCode::F5 => Key::F(5),
+        KeyCode::F6 => Key::F(6),
+        KeyCode::F7 => Key::F(7),
+        KeyCode::F8 => Key::F(8),
+        KeyCode::F9 => Key::F(9),
+        KeyCode::F10 => Key::F(10),
+        KeyCode::F11 => Key::F(11),
+        KeyCode::F12 => Key::F(12),
+        KeyCode::F13 => Key::F(13),
+        KeyCode::F14 => Key::F(14),
+        KeyCode::F15 => Key::F(15),
+        KeyCode::F16 => Key::F(16),
+        KeyCode::F17 => Key::F(17),
+        KeyCode::F18 => Key::F(18),
+        KeyCode::F19 => Key::F(19),
+        KeyCode::F20 => Key::F(20),
+        KeyCode::F21 => Key::F(21),
+        KeyCode::F22 => Key::F(22),
+        KeyCode::F23 => Key::F(23),
+        KeyCode::F24 => Key::F(24),
+        KeyCode::Back => Key::Back,
+        KeyCode::Menu => Key::Menu,
+        _ => Key::Unidentified,
+    }
+}
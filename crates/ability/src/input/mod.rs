@@ -1,17 +1,35 @@
 use std::fmt::Debug;
 
-use ohos_ime_binding::KeyboardStatus;
+use ohos_ime_binding::{Direction, KeyboardStatus, Selection};
 use ohos_xcomponent_binding::{KeyEventData, TouchEventData};
 
+pub use ohos_xcomponent_binding::KeyCode;
+
+mod back;
 mod ime;
+mod keyboard;
+mod mouse;
+mod pointer;
 mod text_input;
+pub use back::*;
 pub use ime::*;
+pub use keyboard::*;
+pub use mouse::*;
+pub use pointer::*;
 pub use text_input::*;
 
 #[derive(Clone)]
 pub enum InputEvent {
     KeyEvent(KeyEventData),
+    /// emitted right after `KeyEvent` with the logical key and modifiers
+    Keyboard(KeyboardEvent),
     TouchEvent(TouchEventData),
+    /// emitted right after `TouchEvent` for the pointer which changed
+    Pointer(PointerEvent),
+    Mouse(MouseEvent),
+    /// `true` when the mouse enters the XComponent, `false` when it leaves
+    Hover(bool),
+    Scroll(ScrollEvent),
     ImeEvent(ImeEvent),
 }
 
@@ -19,7 +37,12 @@ impl Debug for InputEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             InputEvent::KeyEvent(data) => write!(f, "KeyEvent: {:?}", data),
+            InputEvent::Keyboard(data) => write!(f, "Keyboard: {:?}", data),
             InputEvent::TouchEvent(data) => write!(f, "TouchEvent: {:?}", data),
+            InputEvent::Pointer(data) => write!(f, "Pointer: {:?}", data),
+            InputEvent::Mouse(data) => write!(f, "Mouse: {:?}", data),
+            InputEvent::Hover(hover) => write!(f, "Hover: {}", hover),
+            InputEvent::Scroll(data) => write!(f, "Scroll: {:?}", data),
             InputEvent::ImeEvent(data) => write!(f, "ImeEvent: {:?}", data),
         }
     }
@@ -31,6 +54,19 @@ pub enum ImeEvent {
     BackspaceEvent(i32),
     ImeStatusEvent(KeyboardStatus),
     EnterEvent(i32),
+    /// composing text from the IME, an empty `text` clears the preedit.
+    /// `cursor_range` is the byte range of the cursor in `text`,
+    /// `replace_range` is the range of existing text replaced by the preedit, `None` for the cursor position
+    Preedit {
+        text: String,
+        cursor_range: Option<(usize, usize)>,
+        replace_range: Option<(i32, i32)>,
+    },
+    /// final text inserted by the IME while composing, it replaces the current preedit
+    Commit(String),
+    DeleteForward(i32),
+    MoveCursor(Direction),
+    SetSelection(Selection),
 }
 
 impl Debug for ImeEvent {
@@ -40,6 +76,15 @@ impl Debug for ImeEvent {
             ImeEvent::BackspaceEvent(len) => write!(f, "BackspaceEvent: delete length is {}", len),
             ImeEvent::ImeStatusEvent(status) => write!(f, "ImeStatusEvent: {:?}", status),
             ImeEvent::EnterEvent(key) => write!(f, "EnterEvent: {:?}", key),
+            ImeEvent::Preedit {
+                text, cursor_range, ..
+            } => write!(f, "Preedit: {:?} cursor {:?}", text, cursor_range),
+            ImeEvent::Commit(text) => write!(f, "Commit: {:?}", text),
+            ImeEvent::DeleteForward(len) => {
+                write!(f, "DeleteForward: delete length is {}", len)
+            }
+            ImeEvent::MoveCursor(direction) => write!(f, "MoveCursor: {:?}", direction),
+            ImeEvent::SetSelection(selection) => write!(f, "SetSelection: {:?}", selection),
         }
     }
 }
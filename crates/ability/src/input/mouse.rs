@@ -0,0 +1,73 @@
+use crate::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// no button, e.g. a move without any button pressed
+    None,
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+}
+
+impl From<u32> for MouseButton {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => MouseButton::Left,
+            2 => MouseButton::Right,
+            4 => MouseButton::Middle,
+            8 => MouseButton::Back,
+            16 => MouseButton::Forward,
+            _ => MouseButton::None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseAction {
+    Press,
+    Release,
+    Move,
+}
+
+impl MouseAction {
+    pub(crate) fn from_raw(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(MouseAction::Press),
+            2 => Some(MouseAction::Release),
+            3 => Some(MouseAction::Move),
+            _ => None,
+        }
+    }
+}
+
+/// Mouse button or move event from XComponent
+#[derive(Debug, Clone, PartialEq)]
+pub struct MouseEvent {
+    pub button: MouseButton,
+    pub action: MouseAction,
+    /// position relative to the XComponent in vp
+    pub position: Point,
+    /// position relative to the XComponent in px
+    pub physical_position: Point,
+    /// position relative to the screen in px
+    pub screen_position: Point,
+    /// event time in nanoseconds
+    pub timestamp: i64,
+}
+
+/// Mouse wheel or touchpad axis event from XComponent
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrollEvent {
+    /// horizontal axis value reported by the system, positive to the right
+    pub delta_x: f64,
+    /// vertical axis value reported by the system, positive downwards
+    pub delta_y: f64,
+    /// pointer position relative to the XComponent in vp
+    pub position: Point,
+    /// pointer position relative to the XComponent in px
+    pub physical_position: Point,
+    /// event time in nanoseconds
+    pub timestamp: i64,
+}
@@ -0,0 +1,110 @@
+use ohos_xcomponent_binding::{TouchEvent, TouchEventData, TouchPointTool, XComponentRaw};
+use ohos_xcomponent_sys::{
+    OH_NativeXComponent_GetTouchPointTiltX, OH_NativeXComponent_GetTouchPointTiltY,
+};
+
+use crate::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerPhase {
+    Down,
+    Move,
+    Up,
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerTool {
+    Finger,
+    /// pen, pencil, brush, airbrush and rubber
+    Pen,
+    Mouse,
+    Unknown,
+}
+
+impl From<TouchPointTool> for PointerTool {
+    fn from(value: TouchPointTool) -> Self {
+        match value {
+            TouchPointTool::Finger => PointerTool::Finger,
+            TouchPointTool::Pen
+            | TouchPointTool::Rubber
+            | TouchPointTool::Brush
+            | TouchPointTool::Pencil
+            | TouchPointTool::Airbrush => PointerTool::Pen,
+            TouchPointTool::Mouse => PointerTool::Mouse,
+            _ => PointerTool::Unknown,
+        }
+    }
+}
+
+/// Platform-neutral pointer event for the pointer which triggered a touch event
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerEvent {
+    /// pointer id, stable from `Down` to `Up`/`Cancel`
+    pub id: i32,
+    pub phase: PointerPhase,
+    /// position relative to the XComponent in vp
+    pub position: Point,
+    /// position relative to the XComponent in px
+    pub physical_position: Point,
+    /// pressure in `0.0..=1.0`, `0.0` when the device doesn't report it
+    pub pressure: f32,
+    /// tilt angles in degrees, `0.0` when the tool isn't a stylus
+    pub tilt_x: f32,
+    pub tilt_y: f32,
+    pub tool: PointerTool,
+    /// event time in nanoseconds
+    pub timestamp: i64,
+}
+
+impl PointerEvent {
+    /// Build the event for the changed pointer of `data`, returns `None` for unknown touch types
+    pub fn from_touch(data: &TouchEventData, scale: f32) -> Option<Self> {
+        let phase = match data.event_type {
+            TouchEvent::Down => PointerPhase::Down,
+            TouchEvent::Move => PointerPhase::Move,
+            TouchEvent::Up => PointerPhase::Up,
+            TouchEvent::Cancel => PointerPhase::Cancel,
+            _ => return None,
+        };
+        let tool = data
+            .touch_points
+            .iter()
+            .find(|p| p.id == data.id)
+            .map(|p| p.event_tool_type.into())
+            .unwrap_or(PointerTool::Unknown);
+        let physical_position = Point::new(data.x, data.y);
+
+        Some(Self {
+            id: data.id,
+            phase,
+            position: physical_position.to_logical(scale),
+            physical_position,
+            pressure: data.force,
+            tilt_x: 0.0,
+            tilt_y: 0.0,
+            tool,
+            timestamp: data.timestamp,
+        })
+    }
+
+    /// Fill the tilt angles, it's only available in the touch callback
+    pub(crate) fn read_tilt(&mut self, xcomponent: XComponentRaw, data: &TouchEventData) {
+        if let Some(index) = data.touch_points.iter().position(|p| p.id == self.id) {
+            let mut tilt_x = 0.0;
+            let mut tilt_y = 0.0;
+            unsafe {
+                if OH_NativeXComponent_GetTouchPointTiltX(xcomponent.0, index as _, &mut tilt_x)
+                    == 0
+                {
+                    self.tilt_x = tilt_x;
+                }
+                if OH_NativeXComponent_GetTouchPointTiltY(xcomponent.0, index as _, &mut tilt_y)
+                    == 0
+                {
+                    self.tilt_y = tilt_y;
+                }
+            }
+        }
+    }
+}
@@ -1,36 +1,53 @@
+#[macro_use]
+mod logger;
+
 mod app;
 mod area;
 mod configuration;
+mod crash;
 mod draw;
 mod error;
 mod event;
 mod helper;
 mod input;
 mod lifecycle;
+mod main_thread;
 mod memory;
 mod render;
 mod resource;
 mod stage;
 mod waker;
 
+#[cfg(feature = "async")]
+mod executor;
+#[cfg(feature = "gestures")]
+mod gestures;
 #[cfg(feature = "webview")]
 mod webview;
 
 pub use app::*;
 pub use area::*;
 pub use configuration::*;
+pub use crash::*;
 pub use draw::*;
 pub use error::*;
 pub use event::*;
 pub use helper::*;
 pub use input::*;
 pub use lifecycle::*;
+pub use main_thread::*;
 pub use memory::*;
 pub use render::*;
 pub use resource::*;
 pub use stage::*;
 pub use waker::*;
 
+#[cfg(feature = "async")]
+pub use executor::*;
+#[cfg(feature = "gestures")]
+pub use gestures::*;
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub use logger::*;
 #[cfg(feature = "webview")]
 pub use webview::*;
 
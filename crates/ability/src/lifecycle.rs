@@ -7,8 +7,9 @@ use napi_ohos::{
 };
 
 use crate::{
-    AvoidArea, AvoidAreaInfo, AvoidAreaType, ContentRect, Event, OpenHarmonyApp, Rect, SaveLoader,
-    SaveSaver, Size, StageEventType, WAKER,
+    cancel_pending_permission_requests, catch_panic, create_main_thread_tsfn, AvoidArea,
+    AvoidAreaInfo, AvoidAreaType, ContentRect, Event, MemoryLevel, OpenHarmonyApp, Rect,
+    SaveLoader, SaveSaver, Size, StageEventType, WAKER,
 };
 
 #[napi(object)]
@@ -63,11 +64,16 @@ pub fn create_lifecycle_handle<'a>(
 ) -> Result<ApplicationLifecycle<'a>> {
     let waker_app = app.clone();
     let waker: Function<'_, (), ()> = env.create_function_from_closure("waker", move |_ctx| {
-        if let Some(ref mut h) = *waker_app.event_loop.borrow_mut() {
-            h(Event::UserEvent)
-        }
+        catch_panic("waker", || {
+            // redraw requests from other threads can only arm the frame callback here
+            waker_app.inner.write().unwrap().sync_frame_callback();
 
-        Ok(())
+            if let Some(ref mut h) = *waker_app.event_loop.borrow_mut() {
+                h(Event::UserEvent)
+            }
+
+            Ok(())
+        })
     })?;
 
     let tsfn = waker
@@ -83,206 +89,253 @@ pub fn create_lifecycle_handle<'a>(
         guard.replace(Arc::new(tsfn));
     }
 
+    create_main_thread_tsfn(env)?;
+
     let on_memory_level_app = app.clone();
     let on_memory_level: Function<'_, i32, ()> =
-        env.create_function_from_closure("memory_level", move |_ctx| {
-            if let Some(ref mut h) = *on_memory_level_app.event_loop.borrow_mut() {
-                h(Event::LowMemory)
-            }
-            Ok(())
+        env.create_function_from_closure("memory_level", move |ctx| {
+            catch_panic("memory_level", || {
+                let level = MemoryLevel::from(ctx.first_arg::<i32>()?);
+                log_info!("memory level: {:?}", level);
+                on_memory_level_app.set_memory_level(level);
+                Ok(())
+            })
         })?;
 
     let configuration_updated_app = app.clone();
     let on_configuration_updated =
         env.create_function_from_closure("configuration_updated", move |ctx| {
-            let configuration = ctx.first_arg::<Object>()?;
-            let language = configuration.get_named_property::<String>("language")?;
-            let color_mode = configuration.get_named_property::<i32>("colorMode")?;
-            let direction = configuration.get_named_property::<i32>("direction")?;
-            let screen_density = configuration.get_named_property::<i32>("screenDensity")?;
-            let display_id = configuration.get_named_property::<i32>("displayId")?;
-            let has_pointer_device =
-                configuration.get_named_property::<bool>("hasPointerDevice")?;
-            let font_size_scale = configuration.get_named_property::<f64>("fontSizeScale")?;
-            let font_weight_scale = configuration.get_named_property::<f64>("fontWeightScale")?;
-            let mcc = configuration.get_named_property::<String>("mcc")?;
-            let mnc = configuration.get_named_property::<String>("mnc")?;
-
-            let configuration = crate::Configuration {
-                language,
-                color_mode: color_mode.into(),
-                direction: direction.into(),
-                screen_density: screen_density.into(),
-                display_id,
-                has_pointer_device,
-                font_size_scale,
-                font_weight_scale,
-                mcc,
-                mnc,
-            };
-            configuration_updated_app
-                .inner
-                .write()
-                .unwrap()
-                .configuration = configuration.clone();
-            let conf = configuration.clone();
-            if let Some(ref mut h) = *configuration_updated_app.event_loop.borrow_mut() {
-                h(Event::ConfigChanged(conf))
-            }
-            Ok(())
+            catch_panic("configuration_updated", || {
+                let configuration = ctx.first_arg::<Object>()?;
+                let language = configuration.get_named_property::<String>("language")?;
+                let color_mode = configuration.get_named_property::<i32>("colorMode")?;
+                let direction = configuration.get_named_property::<i32>("direction")?;
+                let screen_density = configuration.get_named_property::<i32>("screenDensity")?;
+                let display_id = configuration.get_named_property::<i32>("displayId")?;
+                let has_pointer_device =
+                    configuration.get_named_property::<bool>("hasPointerDevice")?;
+                let font_size_scale = configuration.get_named_property::<f64>("fontSizeScale")?;
+                let font_weight_scale =
+                    configuration.get_named_property::<f64>("fontWeightScale")?;
+                let mcc = configuration.get_named_property::<String>("mcc")?;
+                let mnc = configuration.get_named_property::<String>("mnc")?;
+
+                let configuration = crate::Configuration {
+                    language,
+                    color_mode: color_mode.into(),
+                    direction: direction.into(),
+                    screen_density: screen_density.into(),
+                    display_id,
+                    has_pointer_device,
+                    font_size_scale,
+                    font_weight_scale,
+                    mcc,
+                    mnc,
+                };
+                configuration_updated_app
+                    .inner
+                    .write()
+                    .unwrap()
+                    .configuration = configuration.clone();
+                let conf = configuration.clone();
+                if let Some(ref mut h) = *configuration_updated_app.event_loop.borrow_mut() {
+                    h(Event::ConfigChanged(conf))
+                }
+                Ok(())
+            })
         })?;
 
     let window_stage_event_app = app.clone();
     let window_stage_event =
         env.create_function_from_closure("window_stage_event", move |ctx| {
-            let event_type = ctx.first_arg::<i32>()?;
+            catch_panic("window_stage_event", || {
+                let event_type = ctx.first_arg::<i32>()?;
 
-            if let Some(ref mut h) = *window_stage_event_app.event_loop.borrow_mut() {
                 let state_event = StageEventType::from(event_type);
-                let e = match state_event {
-                    StageEventType::Shown => Event::Start,
-                    StageEventType::Active => Event::GainedFocus,
-                    StageEventType::Inactive => Event::LostFocus,
-                    StageEventType::Hidden => Event::Stop,
-                    StageEventType::Resumed => Event::Resume(SaveLoader {
-                        app: &window_stage_event_app,
-                    }),
-                    StageEventType::Paused => Event::Pause,
-                };
-                h(e)
-            }
-            Ok(())
+                log_debug!("window stage event: {:?}", state_event);
+                if matches!(state_event, StageEventType::Inactive) {
+                    // key releases are not delivered after the window loses focus
+                    window_stage_event_app
+                        .inner
+                        .write()
+                        .unwrap()
+                        .keyboard
+                        .reset();
+                }
+
+                if let Some(ref mut h) = *window_stage_event_app.event_loop.borrow_mut() {
+                    let e = match state_event {
+                        StageEventType::Shown => Event::Start,
+                        StageEventType::Active => Event::GainedFocus,
+                        StageEventType::Inactive => Event::LostFocus,
+                        StageEventType::Hidden => Event::Stop,
+                        StageEventType::Resumed => Event::Resume(SaveLoader {
+                            app: &window_stage_event_app,
+                        }),
+                        StageEventType::Paused => Event::Pause,
+                    };
+                    h(e)
+                }
+                Ok(())
+            })
         })?;
 
     // TODO: we may can remove it
     let window_resize_app = app.clone();
     let window_resize = env.create_function_from_closure("window_resize", move |ctx| {
-        let size = ctx.first_arg::<Object>()?;
-        let width = size.get_named_property::<i32>("width")?;
-        let height = size.get_named_property::<i32>("height")?;
-
-        if let Some(ref mut h) = *window_resize_app.event_loop.borrow_mut() {
-            h(Event::WindowResize(Size { width, height }))
-        }
-        Ok(())
+        catch_panic("window_resize", || {
+            let size = ctx.first_arg::<Object>()?;
+            let width = size.get_named_property::<i32>("width")?;
+            let height = size.get_named_property::<i32>("height")?;
+
+            if let Some(ref mut h) = *window_resize_app.event_loop.borrow_mut() {
+                h(Event::WindowResized(Size { width, height }))
+            }
+            Ok(())
+        })
     })?;
 
     // TODO: we may can remove it
     let window_rect_app = app.clone();
     let window_rect_change =
         env.create_function_from_closure("window_rect_change", move |ctx| {
-            let options = ctx.first_arg::<Object>()?;
-            let reason = options.get_named_property::<i32>("reason")?;
-            let rect = parse_rect(options.get_named_property::<Object>("rect")?)?;
-            window_rect_app.inner.write().unwrap().window_rect = rect;
-
-            if let Some(ref mut h) = *window_rect_app.event_loop.borrow_mut() {
-                h(Event::ContentRectChange(ContentRect {
-                    reason: reason.into(),
-                    rect,
-                }))
-            }
-            Ok(())
+            catch_panic("window_rect_change", || {
+                let options = ctx.first_arg::<Object>()?;
+                let reason = options.get_named_property::<i32>("reason")?;
+                let rect = parse_rect(options.get_named_property::<Object>("rect")?)?;
+                window_rect_app.inner.write().unwrap().window_rect = rect;
+
+                if let Some(ref mut h) = *window_rect_app.event_loop.borrow_mut() {
+                    h(Event::ContentRectChange(ContentRect {
+                        reason: reason.into(),
+                        rect,
+                    }))
+                }
+                Ok(())
+            })
         })?;
 
     let avoid_area_change_app = app.clone();
     let avoid_area_change = env.create_function_from_closure("avoid_area_change", move |ctx| {
-        let options = ctx.first_arg::<Object>()?;
-        let area_type = AvoidAreaType::from(options.get_named_property::<i32>("type")?);
-        let area = options.get_named_property::<Object>("area")?;
-        let visible = area.get_named_property::<bool>("visible")?;
-        let avoid_area = AvoidArea {
-            visible,
-            left_rect: parse_rect(area.get_named_property::<Object>("leftRect")?)?,
-            top_rect: parse_rect(area.get_named_property::<Object>("topRect")?)?,
-            right_rect: parse_rect(area.get_named_property::<Object>("rightRect")?)?,
-            bottom_rect: parse_rect(area.get_named_property::<Object>("bottomRect")?)?,
-        };
-
-        {
-            let mut inner = avoid_area_change_app.inner.write().unwrap();
-            inner.avoid_areas.insert(area_type, avoid_area);
-        }
-
-        if let Some(ref mut h) = *avoid_area_change_app.event_loop.borrow_mut() {
-            h(Event::AvoidAreaChange(AvoidAreaInfo {
-                area_type,
-                area: avoid_area,
-            }))
-        }
-        Ok(())
+        catch_panic("avoid_area_change", || {
+            let options = ctx.first_arg::<Object>()?;
+            let area_type = AvoidAreaType::from(options.get_named_property::<i32>("type")?);
+            let area = options.get_named_property::<Object>("area")?;
+            let visible = area.get_named_property::<bool>("visible")?;
+            let avoid_area = AvoidArea {
+                visible,
+                left_rect: parse_rect(area.get_named_property::<Object>("leftRect")?)?,
+                top_rect: parse_rect(area.get_named_property::<Object>("topRect")?)?,
+                right_rect: parse_rect(area.get_named_property::<Object>("rightRect")?)?,
+                bottom_rect: parse_rect(area.get_named_property::<Object>("bottomRect")?)?,
+            };
+
+            {
+                let mut inner = avoid_area_change_app.inner.write().unwrap();
+                inner.avoid_areas.insert(area_type, avoid_area);
+            }
+
+            if let Some(ref mut h) = *avoid_area_change_app.event_loop.borrow_mut() {
+                h(Event::AvoidAreaChange(AvoidAreaInfo {
+                    area_type,
+                    area: avoid_area,
+                }))
+            }
+            if area_type == AvoidAreaType::Keyboard {
+                let height = avoid_area.bottom_rect.height;
+                avoid_area_change_app.set_keyboard_visibility(visible && height > 0, Some(height));
+            }
+            Ok(())
+        })
     })?;
 
     let on_window_stage_create_app = app.clone();
     let on_window_stage_create =
         env.create_function_from_closure("on_ability_create", move |_ctx| {
-            if let Some(ref mut h) = *on_window_stage_create_app.event_loop.borrow_mut() {
-                h(Event::WindowCreate)
-            }
-            Ok(())
+            catch_panic("on_ability_create", || {
+                log_info!("window stage created");
+                if let Some(ref mut h) = *on_window_stage_create_app.event_loop.borrow_mut() {
+                    h(Event::WindowCreate)
+                }
+                Ok(())
+            })
         })?;
 
     let on_window_stage_destroy_app = app.clone();
     let on_window_stage_destroy =
         env.create_function_from_closure("on_window_stage_destroy", move |_ctx| {
-            if let Some(ref mut h) = *on_window_stage_destroy_app.event_loop.borrow_mut() {
-                h(Event::WindowDestroy)
-            }
-            Ok(())
+            catch_panic("on_window_stage_destroy", || {
+                log_info!("window stage destroyed");
+                if let Some(ref mut h) = *on_window_stage_destroy_app.event_loop.borrow_mut() {
+                    h(Event::WindowDestroy)
+                }
+                Ok(())
+            })
         })?;
 
     let on_ability_create_app = app.clone();
     let on_ability_create = env.create_function_from_closure("on_ability_create", move |_ctx| {
-        if let Some(ref mut h) = *on_ability_create_app.event_loop.borrow_mut() {
-            h(Event::Create)
-        }
-        Ok(())
+        catch_panic("on_ability_create", || {
+            log_info!("ability created");
+            if let Some(ref mut h) = *on_ability_create_app.event_loop.borrow_mut() {
+                h(Event::Create)
+            }
+            Ok(())
+        })
     })?;
 
     let on_ability_destroy_app = app.clone();
     let on_ability_destroy =
         env.create_function_from_closure("on_ability_destroy", move |_ctx| {
-            if let Some(ref mut h) = *on_ability_destroy_app.event_loop.borrow_mut() {
-                h(Event::Destroy)
-            }
-            Ok(())
+            catch_panic("on_ability_destroy", || {
+                log_info!("ability destroyed");
+                cancel_pending_permission_requests();
+                if let Some(ref mut h) = *on_ability_destroy_app.event_loop.borrow_mut() {
+                    h(Event::Destroy)
+                }
+                Ok(())
+            })
         })?;
 
     let on_ability_restore_state_app = app.clone();
 
     let on_ability_restore_state =
         env.create_function_from_closure("on_ability_restore_state", move |_ctx| {
-            let save_loader = SaveLoader {
-                app: &on_ability_restore_state_app,
-            };
+            catch_panic("on_ability_restore_state", || {
+                let save_loader = SaveLoader {
+                    app: &on_ability_restore_state_app,
+                };
 
-            if let Some(ref mut h) = *on_ability_restore_state_app.event_loop.borrow_mut() {
-                h(Event::Resume(save_loader))
-            }
-            Ok(())
+                if let Some(ref mut h) = *on_ability_restore_state_app.event_loop.borrow_mut() {
+                    h(Event::Resume(save_loader))
+                }
+                Ok(())
+            })
         })?;
 
     let on_ability_save_state_app = app.clone();
     let on_ability_save_state =
         env.create_function_from_closure("on_ability_save_state", move |_ctx| {
-            let save_saver = SaveSaver {
-                app: &on_ability_save_state_app,
-            };
+            catch_panic("on_ability_save_state", || {
+                let save_saver = SaveSaver {
+                    app: &on_ability_save_state_app,
+                };
 
-            if let Some(ref mut h) = *on_ability_save_state_app.event_loop.borrow_mut() {
-                h(Event::SaveState(save_saver))
-            }
-            Ok(())
+                if let Some(ref mut h) = *on_ability_save_state_app.event_loop.borrow_mut() {
+                    h(Event::SaveState(save_saver))
+                }
+                Ok(())
+            })
         })?;
 
     let keyboard_event_callback_app = app.clone();
     let keyboard_event_callback =
         env.create_function_from_closure("keyboard_event_callback", move |ctx| {
-            let event_type = ctx.first_arg::<i32>()?;
-            if let Some(ref mut h) = *keyboard_event_callback_app.event_loop.borrow_mut() {
-                h(Event::KeyboardEvent(event_type))
-            }
-            Ok(())
+            catch_panic("keyboard_event_callback", || {
+                let height = ctx.first_arg::<i32>()?;
+                keyboard_event_callback_app.set_keyboard_visibility(height > 0, Some(height));
+                Ok(())
+            })
         })?;
 
     Ok(ApplicationLifecycle {
@@ -0,0 +1,288 @@
+//! Internal log macros, they forward to `log` and `tracing` with the matching features and
+//! compile to nothing otherwise.
+//! With the `log` feature `HilogLogger` prints `log` records to hilog, with the `tracing` feature
+//! `HilogLayer` prints `tracing` events and their spans. Both print to stderr off device.
+
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        ::log::$level!(target: "openharmony_ability", $($arg)+);
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!(target: "openharmony_ability", $($arg)+);
+        #[cfg(not(any(feature = "log", feature = "tracing")))]
+        {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+macro_rules! log_error {
+    ($($arg:tt)+) => { log_event!(error, $($arg)+) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)+) => { log_event!(warn, $($arg)+) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)+) => { log_event!(info, $($arg)+) };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)+) => { log_event!(debug, $($arg)+) };
+}
+
+#[cfg(feature = "tracing")]
+pub use hilog_layer::*;
+#[cfg(feature = "log")]
+pub use hilog_logger::*;
+
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod hilog {
+    use std::ffi::CString;
+
+    /// Levels hilog prints, it has no trace level
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) enum HilogLevel {
+        Debug,
+        Info,
+        Warn,
+        Error,
+    }
+
+    /// Domain and tag shared by `HilogLogger` and `HilogLayer`
+    pub(crate) struct HilogWriter {
+        domain: u32,
+        tag: CString,
+    }
+
+    impl HilogWriter {
+        pub(crate) fn new(domain: u32, tag: &str) -> Self {
+            Self {
+                domain: domain & 0xFFFF,
+                tag: CString::new(tag.replace('\0', "")).unwrap_or_default(),
+            }
+        }
+
+        #[cfg(target_env = "ohos")]
+        pub(crate) fn print(&self, level: HilogLevel, message: &str) {
+            use ohos_hilogs_sys::{
+                LogLevel_LOG_DEBUG, LogLevel_LOG_ERROR, LogLevel_LOG_INFO, LogLevel_LOG_WARN,
+                LogType_LOG_APP, OH_LOG_Print,
+            };
+
+            let level = match level {
+                HilogLevel::Error => LogLevel_LOG_ERROR,
+                HilogLevel::Warn => LogLevel_LOG_WARN,
+                HilogLevel::Info => LogLevel_LOG_INFO,
+                HilogLevel::Debug => LogLevel_LOG_DEBUG,
+            };
+            let Ok(message) = CString::new(message.replace('\0', "")) else {
+                return;
+            };
+            // the message is passed as an argument, `%` in it isn't parsed as format
+            unsafe {
+                OH_LOG_Print(
+                    LogType_LOG_APP,
+                    level,
+                    self.domain,
+                    self.tag.as_ptr(),
+                    c"%{public}s".as_ptr(),
+                    message.as_ptr(),
+                );
+            }
+        }
+
+        #[cfg(not(target_env = "ohos"))]
+        pub(crate) fn print(&self, level: HilogLevel, message: &str) {
+            let level = match level {
+                HilogLevel::Error => "ERROR",
+                HilogLevel::Warn => "WARN",
+                HilogLevel::Info => "INFO",
+                HilogLevel::Debug => "DEBUG",
+            };
+            // same layout as hilog lines, `<level> <domain>/<tag>: <message>`
+            eprintln!(
+                "{} {:04X}/{}: {}",
+                level,
+                self.domain,
+                self.tag.to_string_lossy(),
+                message
+            );
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+mod hilog_logger {
+    use log::{Log, Metadata, Record, SetLoggerError};
+
+    use super::hilog::{HilogLevel, HilogWriter};
+
+    pub use log::LevelFilter;
+
+    /// Domain and tag of the hilog output, filter with `hilog -D <domain> -T <tag>`
+    #[derive(Debug, Clone)]
+    pub struct HilogConfig {
+        /// 0x0 to 0xFFFF
+        pub domain: u32,
+        /// hilog truncates tags longer than 31 bytes
+        pub tag: String,
+        pub max_level: LevelFilter,
+    }
+
+    impl Default for HilogConfig {
+        fn default() -> Self {
+            Self {
+                domain: 0x0000,
+                tag: "RustAbility".to_string(),
+                max_level: LevelFilter::Info,
+            }
+        }
+    }
+
+    /// `log` implementation printing to hilog, or to stderr on other targets for tests
+    pub struct HilogLogger {
+        writer: HilogWriter,
+        max_level: LevelFilter,
+    }
+
+    impl HilogLogger {
+        pub fn new(config: HilogConfig) -> Self {
+            Self {
+                writer: HilogWriter::new(config.domain, &config.tag),
+                max_level: config.max_level,
+            }
+        }
+    }
+
+    impl Log for HilogLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= self.max_level
+        }
+
+        fn log(&self, record: &Record) {
+            if !self.enabled(record.metadata()) {
+                return;
+            }
+            let level = match record.level() {
+                log::Level::Error => HilogLevel::Error,
+                log::Level::Warn => HilogLevel::Warn,
+                log::Level::Info => HilogLevel::Info,
+                log::Level::Debug | log::Level::Trace => HilogLevel::Debug,
+            };
+            let message = format!("[{}] {}", record.target(), record.args());
+            self.writer.print(level, &message);
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Install `HilogLogger` as the global `log` logger, fails if a logger is already set
+    pub fn init_logger(config: HilogConfig) -> Result<(), SetLoggerError> {
+        let max_level = config.max_level;
+        log::set_boxed_logger(Box::new(HilogLogger::new(config)))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod hilog_layer {
+    use std::fmt::{self, Write};
+
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id},
+        Event, Level, Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+    use super::hilog::{HilogLevel, HilogWriter};
+
+    /// `tracing` layer printing events to hilog, prefixed with the spans they happened in.
+    /// Filter it with the subscriber, e.g. `HilogLayer::new(0x0000, "RustAbility").with_filter(LevelFilter::INFO)`.
+    pub struct HilogLayer {
+        writer: HilogWriter,
+    }
+
+    impl HilogLayer {
+        /// `domain` is 0x0 to 0xFFFF, hilog truncates tags longer than 31 bytes
+        pub fn new(domain: u32, tag: &str) -> Self {
+            Self {
+                writer: HilogWriter::new(domain, tag),
+            }
+        }
+    }
+
+    /// `message` first, then the other fields as `name=value`
+    #[derive(Default)]
+    struct FieldsVisitor {
+        message: String,
+        fields: String,
+    }
+
+    impl Visit for FieldsVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                let _ = write!(self.message, "{:?}", value);
+            } else {
+                let _ = write!(self.fields, " {}={:?}", field.name(), value);
+            }
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "message" {
+                self.message.push_str(value);
+            } else {
+                let _ = write!(self.fields, " {}={}", field.name(), value);
+            }
+        }
+    }
+
+    /// Formatted fields of a span, stored in its extensions
+    struct SpanFields(String);
+
+    impl<S> Layer<S> for HilogLayer
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let Some(span) = ctx.span(id) else {
+                return;
+            };
+            let mut visitor = FieldsVisitor::default();
+            attrs.record(&mut visitor);
+            span.extensions_mut()
+                .insert(SpanFields(visitor.fields.trim_start().to_string()));
+        }
+
+        fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+            let level = match *event.metadata().level() {
+                Level::ERROR => HilogLevel::Error,
+                Level::WARN => HilogLevel::Warn,
+                Level::INFO => HilogLevel::Info,
+                Level::DEBUG | Level::TRACE => HilogLevel::Debug,
+            };
+
+            let mut line = format!("[{}] ", event.metadata().target());
+            if let Some(scope) = ctx.event_scope(event) {
+                for span in scope.from_root() {
+                    line.push_str(span.name());
+                    if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                        if !fields.is_empty() {
+                            let _ = write!(line, "{{{}}}", fields);
+                        }
+                    }
+                    line.push_str(": ");
+                }
+            }
+
+            let mut visitor = FieldsVisitor::default();
+            event.record(&mut visitor);
+            line.push_str(&visitor.message);
+            line.push_str(&visitor.fields);
+            self.writer.print(level, &line);
+        }
+    }
+}
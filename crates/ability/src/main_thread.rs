@@ -0,0 +1,190 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, LazyLock, Mutex, OnceLock, RwLock,
+    },
+    task::{Context, Wake, Waker},
+    thread::{self, ThreadId},
+};
+
+use napi_ohos::{
+    bindgen_prelude::Function,
+    threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+    Env, Error, Result, Status,
+};
+
+use crate::{catch_panic, AbilityError};
+
+type MainThreadTask = Box<dyn FnOnce() + Send>;
+
+type MainThreadTsfn = LazyLock<RwLock<Option<Arc<ThreadsafeFunction<(), ()>>>>>;
+
+/// Tasks posted from any thread, drained on the main thread
+static MAIN_THREAD_TASKS: LazyLock<Mutex<VecDeque<MainThreadTask>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+static MAIN_THREAD_TSFN: MainThreadTsfn = LazyLock::new(|| RwLock::new(None));
+
+static MAIN_THREAD_ID: OnceLock<ThreadId> = OnceLock::new();
+
+type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+    /// Futures of `spawn_local`, only touched on the main thread
+    static LOCAL_TASKS: RefCell<HashMap<u64, (LocalFuture, Arc<LocalTaskWaker>)>> =
+        RefCell::new(HashMap::new());
+
+    static NEXT_LOCAL_TASK: RefCell<u64> = const { RefCell::new(0) };
+}
+
+/// Create the threadsafe function which runs posted tasks on the main thread
+pub(crate) fn create_main_thread_tsfn(env: &Env) -> Result<()> {
+    let _ = MAIN_THREAD_ID.set(thread::current().id());
+
+    let run_tasks: Function<'_, (), ()> =
+        env.create_function_from_closure("main_thread_tasks", move |_ctx| {
+            loop {
+                // release the lock before running, a task may post another task
+                let task = MAIN_THREAD_TASKS
+                    .lock()
+                    .expect("Failed to lock MAIN_THREAD_TASKS")
+                    .pop_front();
+                let Some(task) = task else {
+                    break;
+                };
+                // a panicking task is dropped, the tasks queued after it still run
+                if let Err(err) = catch_panic("main_thread_task", || {
+                    task();
+                    Ok(())
+                }) {
+                    log_error!("{}", err);
+                }
+            }
+            Ok(())
+        })?;
+
+    let tsfn = run_tasks
+        .build_threadsafe_function()
+        .callee_handled::<true>()
+        .build()?;
+
+    MAIN_THREAD_TSFN
+        .write()
+        .map_err(|_| Error::from_reason("Failed to write MAIN_THREAD_TSFN"))?
+        .replace(Arc::new(tsfn));
+    Ok(())
+}
+
+/// Queue `task` to run on the main thread, also when called from the main thread
+pub(crate) fn post_to_main_thread(task: MainThreadTask) -> Result<()> {
+    let tsfn = MAIN_THREAD_TSFN
+        .read()
+        .map_err(|_| Error::from_reason("Failed to read MAIN_THREAD_TSFN"))?
+        .clone()
+        .ok_or_else(|| Error::from_reason("main thread function is not initialized"))?;
+
+    // hold the lock until the call is queued, so a failed call pops its own task
+    let mut tasks = MAIN_THREAD_TASKS
+        .lock()
+        .expect("Failed to lock MAIN_THREAD_TASKS");
+    tasks.push_back(task);
+    let status = tsfn.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+    if status != Status::Ok {
+        tasks.pop_back();
+        return Err(Error::from_reason(format!(
+            "call main thread function failed with status: {:?}",
+            status
+        )));
+    }
+    Ok(())
+}
+
+/// Whether the current thread is the ArkTS main thread, where napi values and the helper are usable
+pub fn is_main_thread() -> bool {
+    MAIN_THREAD_ID
+        .get()
+        .is_some_and(|id| *id == thread::current().id())
+}
+
+/// Run `f` on the main thread from any thread.
+/// It's queued even when called on the main thread and runs after the current callback returns.
+pub fn run_on_main_thread<F>(f: F) -> Result<()>
+where
+    F: FnOnce() + Send + 'static,
+{
+    post_to_main_thread(Box::new(f))
+}
+
+/// Run a future on the main thread, so it can hold napi values, webviews or the helper.
+/// Must be called on the main thread, use `run_on_main_thread` to spawn from other threads.
+pub fn spawn_local<F>(future: F) -> Result<()>
+where
+    F: Future<Output = ()> + 'static,
+{
+    if !is_main_thread() {
+        return Err(Error::from_reason(
+            AbilityError::OnlyRunWithMainThread("spawn_local".to_string()).to_string(),
+        ));
+    }
+
+    let id = NEXT_LOCAL_TASK.with(|next| {
+        let mut next = next.borrow_mut();
+        *next += 1;
+        *next
+    });
+    let waker = Arc::new(LocalTaskWaker {
+        id,
+        scheduled: AtomicBool::new(false),
+    });
+    LOCAL_TASKS.with(|tasks| {
+        tasks
+            .borrow_mut()
+            .insert(id, (Box::pin(future), waker.clone()))
+    });
+    // the first poll is queued like a wake, so the caller finishes first
+    waker.scheduled.store(true, Ordering::SeqCst);
+    post_to_main_thread(Box::new(move || poll_local_task(id))).inspect_err(|_| {
+        LOCAL_TASKS.with(|tasks| tasks.borrow_mut().remove(&id));
+    })
+}
+
+/// Poll a `spawn_local` future, it's taken out of the map while polling so it may spawn others.
+/// A future that panics while polled is dropped with the unwind.
+fn poll_local_task(id: u64) {
+    let Some((mut future, waker)) = LOCAL_TASKS.with(|tasks| tasks.borrow_mut().remove(&id)) else {
+        return;
+    };
+    waker.scheduled.store(false, Ordering::SeqCst);
+
+    let task_waker = Waker::from(waker.clone());
+    let mut cx = Context::from_waker(&task_waker);
+    if future.as_mut().poll(&mut cx).is_pending() {
+        LOCAL_TASKS.with(|tasks| tasks.borrow_mut().insert(id, (future, waker)));
+    }
+}
+
+struct LocalTaskWaker {
+    id: u64,
+    /// a poll is already queued, further wakes before it runs are merged
+    scheduled: AtomicBool,
+}
+
+impl Wake for LocalTaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if self.scheduled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let id = self.id;
+        if post_to_main_thread(Box::new(move || poll_local_task(id))).is_err() {
+            self.scheduled.store(false, Ordering::SeqCst);
+        }
+    }
+}
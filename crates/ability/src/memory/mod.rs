@@ -1,5 +1,63 @@
+use std::{cell::RefCell, rc::Rc};
+
+/// System memory pressure, ordered from none to the most severe
+/// https://developer.huawei.com/consumer/cn/doc/harmonyos-references-V5/js-apis-app-ability-abilityconstant-V5#memorylevel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum MemoryLevel {
-    Low,
+    /// no pressure reported yet
+    #[default]
     Normal,
-    High,
+    /// MEMORY_LEVEL_MODERATE
+    Moderate,
+    /// MEMORY_LEVEL_LOW
+    Low,
+    /// MEMORY_LEVEL_CRITICAL, the process is likely to be killed next
+    Critical,
+}
+
+impl From<i32> for MemoryLevel {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => MemoryLevel::Moderate,
+            1 => MemoryLevel::Low,
+            2 => MemoryLevel::Critical,
+            _ => MemoryLevel::Normal,
+        }
+    }
+}
+
+/// Returned by `OpenHarmonyApp::add_memory_trim_callback`, used to remove the callback again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryTrimId(u64);
+
+type MemoryTrimCallback = Rc<RefCell<Box<dyn FnMut(MemoryLevel) + 'static>>>;
+
+/// Trim callbacks in registration order
+#[derive(Default)]
+pub(crate) struct MemoryTrimCallbacks {
+    next_id: u64,
+    callbacks: Vec<(MemoryTrimId, MemoryTrimCallback)>,
+}
+
+impl MemoryTrimCallbacks {
+    pub(crate) fn add(&mut self, callback: Box<dyn FnMut(MemoryLevel) + 'static>) -> MemoryTrimId {
+        let id = MemoryTrimId(self.next_id);
+        self.next_id += 1;
+        self.callbacks.push((id, Rc::new(RefCell::new(callback))));
+        id
+    }
+
+    pub(crate) fn remove(&mut self, id: MemoryTrimId) -> bool {
+        let len = self.callbacks.len();
+        self.callbacks.retain(|(callback_id, _)| *callback_id != id);
+        self.callbacks.len() != len
+    }
+
+    /// Cloned so a callback may add or remove callbacks while it runs
+    pub(crate) fn snapshot(&self) -> Vec<MemoryTrimCallback> {
+        self.callbacks
+            .iter()
+            .map(|(_, callback)| callback.clone())
+            .collect()
+    }
 }
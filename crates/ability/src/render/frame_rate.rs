@@ -0,0 +1,79 @@
+use ohos_display_binding::default_display_refresh_rate;
+
+use crate::AbilityError;
+
+const FALLBACK_REFRESH_RATE: i32 = 60;
+
+/// Expected frame rate range for the XComponent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameRateRange {
+    pub min: i32,
+    pub max: i32,
+    pub expected: i32,
+}
+
+impl FrameRateRange {
+    pub fn new(min: i32, max: i32, expected: i32) -> Self {
+        Self { min, max, expected }
+    }
+
+    /// Check that `0 <= min <= expected <= max`
+    pub fn validate(&self) -> Result<(), AbilityError> {
+        if self.min < 0 || self.min > self.expected || self.expected > self.max {
+            return Err(AbilityError::InvalidFrameRate(*self));
+        }
+        Ok(())
+    }
+}
+
+impl From<(i32, i32, i32)> for FrameRateRange {
+    fn from((min, max, expected): (i32, i32, i32)) -> Self {
+        Self::new(min, max, expected)
+    }
+}
+
+/// Frame rate presets for `set_frame_rate`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameRate {
+    /// 15-30 fps, for mostly static content
+    Low,
+    /// 30-60 fps
+    Balanced,
+    /// 60-120 fps, for games and animations
+    High,
+    /// Follow the refresh rate of the default display
+    Display,
+    Custom(FrameRateRange),
+}
+
+impl FrameRate {
+    /// Resolve the preset into a concrete range.
+    /// `Display` queries the default display every time it is resolved.
+    pub fn range(&self) -> FrameRateRange {
+        match self {
+            FrameRate::Low => FrameRateRange::new(15, 30, 30),
+            FrameRate::Balanced => FrameRateRange::new(30, 60, 60),
+            FrameRate::High => FrameRateRange::new(60, 120, 120),
+            FrameRate::Display => {
+                let refresh_rate = match default_display_refresh_rate() as i32 {
+                    rate if rate > 0 => rate,
+                    _ => FALLBACK_REFRESH_RATE,
+                };
+                FrameRateRange::new(refresh_rate, refresh_rate, refresh_rate)
+            }
+            FrameRate::Custom(range) => *range,
+        }
+    }
+}
+
+impl From<FrameRateRange> for FrameRate {
+    fn from(value: FrameRateRange) -> Self {
+        FrameRate::Custom(value)
+    }
+}
+
+impl From<(i32, i32, i32)> for FrameRate {
+    fn from(value: (i32, i32, i32)) -> Self {
+        FrameRate::Custom(value.into())
+    }
+}
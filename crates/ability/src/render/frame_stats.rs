@@ -0,0 +1,132 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{FrameRateRange, IntervalInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStatsConfig {
+    /// Number of frames kept in the rolling window
+    pub window: usize,
+    /// Emit `Event::FrameStats` with this interval, measured in vsync time.
+    /// `None` disables the periodic event, stats can still be read with `frame_stats()`.
+    pub report_interval: Option<Duration>,
+}
+
+impl Default for FrameStatsConfig {
+    fn default() -> Self {
+        Self {
+            window: 120,
+            report_interval: None,
+        }
+    }
+}
+
+/// Snapshot of frame pacing statistics
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameStats {
+    /// Frames observed since the collector was enabled
+    pub frame_count: u64,
+    /// Frames that arrived after the previous frame's target time stamp
+    pub missed_deadlines: u64,
+    /// Rolling frames per second over the window
+    pub fps: f64,
+    pub frame_time_p50: Duration,
+    pub frame_time_p95: Duration,
+    pub frame_time_p99: Duration,
+    pub frame_time_max: Duration,
+    /// Latest range passed to `set_frame_rate`
+    pub frame_rate: Option<FrameRateRange>,
+}
+
+#[derive(Clone)]
+pub(crate) struct FrameStatsCollector {
+    config: FrameStatsConfig,
+    frame_times: VecDeque<u64>,
+    last_frame: Option<IntervalInfo>,
+    last_report: Option<i64>,
+    frame_count: u64,
+    missed_deadlines: u64,
+}
+
+impl FrameStatsCollector {
+    pub(crate) fn new(config: FrameStatsConfig) -> Self {
+        Self {
+            frame_times: VecDeque::with_capacity(config.window),
+            config,
+            last_frame: None,
+            last_report: None,
+            frame_count: 0,
+            missed_deadlines: 0,
+        }
+    }
+
+    /// Record a vsync and return whether a periodic report is due.
+    pub(crate) fn record(&mut self, info: &IntervalInfo) -> bool {
+        self.frame_count += 1;
+
+        if let Some(last) = self.last_frame.as_ref() {
+            let frame_time = info.time_stamp.saturating_sub(last.time_stamp).max(0) as u64;
+            // A frame is late when it lands more than half a vsync period after
+            // the previous frame's target.
+            let period = last
+                .target_time_stamp
+                .saturating_sub(last.time_stamp)
+                .max(0);
+            if info.time_stamp > last.target_time_stamp + period / 2 {
+                self.missed_deadlines += 1;
+            }
+
+            if self.frame_times.len() == self.config.window.max(1) {
+                self.frame_times.pop_front();
+            }
+            self.frame_times.push_back(frame_time);
+        }
+        self.last_frame = Some(info.clone());
+
+        let Some(interval) = self.config.report_interval else {
+            return false;
+        };
+        let last_report = *self.last_report.get_or_insert(info.time_stamp);
+        if info.time_stamp.saturating_sub(last_report) >= interval.as_nanos() as i64 {
+            self.last_report = Some(info.time_stamp);
+            return true;
+        }
+        false
+    }
+
+    /// Forget the previous frame, used when the frame callback is unregistered so
+    /// that the idle gap is not counted as a long frame.
+    pub(crate) fn interrupt(&mut self) {
+        self.last_frame = None;
+    }
+
+    pub(crate) fn snapshot(&self, frame_rate: Option<FrameRateRange>) -> FrameStats {
+        let mut sorted: Vec<u64> = self.frame_times.iter().copied().collect();
+        sorted.sort_unstable();
+
+        let percentile = |p: f64| -> Duration {
+            if sorted.is_empty() {
+                return Duration::ZERO;
+            }
+            let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+            Duration::from_nanos(sorted[index])
+        };
+
+        let total: u64 = sorted.iter().sum();
+        let fps = if total == 0 {
+            0.0
+        } else {
+            sorted.len() as f64 / Duration::from_nanos(total).as_secs_f64()
+        };
+
+        FrameStats {
+            frame_count: self.frame_count,
+            missed_deadlines: self.missed_deadlines,
+            fps,
+            frame_time_p50: percentile(0.5),
+            frame_time_p95: percentile(0.95),
+            frame_time_p99: percentile(0.99),
+            frame_time_max: Duration::from_nanos(sorted.last().copied().unwrap_or_default()),
+            frame_rate,
+        }
+    }
+}
@@ -1,3 +1,9 @@
+mod frame_rate;
+mod frame_stats;
+mod mouse;
 mod xcomponent;
 
+pub use frame_rate::*;
+pub use frame_stats::*;
+pub(crate) use mouse::*;
 pub use xcomponent::*;
@@ -0,0 +1,123 @@
+use std::{cell::RefCell, mem::MaybeUninit, os::raw::c_void};
+
+use napi_ohos::{Error, Result};
+use ohos_arkui_sys::{
+    ArkUI_UIInputEvent, ArkUI_UIInputEvent_Type,
+    ArkUI_UIInputEvent_Type_ARKUI_UIINPUTEVENT_TYPE_AXIS,
+    OH_ArkUI_AxisEvent_GetHorizontalAxisValue, OH_ArkUI_AxisEvent_GetVerticalAxisValue,
+    OH_ArkUI_PointerEvent_GetX, OH_ArkUI_PointerEvent_GetY, OH_ArkUI_UIInputEvent_GetEventTime,
+};
+use ohos_xcomponent_sys::{
+    OH_NativeXComponent, OH_NativeXComponent_GetMouseEvent, OH_NativeXComponent_MouseEvent,
+    OH_NativeXComponent_MouseEvent_Callback, OH_NativeXComponent_RegisterMouseEventCallback,
+    OH_NativeXComponent_RegisterUIInputEventCallback,
+};
+
+use crate::{
+    catch_panic, Event, InputEvent, MouseAction, MouseEvent, OpenHarmonyApp, Point, ScrollEvent,
+};
+
+thread_local! {
+    // native mouse callbacks don't carry user data, keep the app for them
+    static MOUSE_APP: RefCell<Option<OpenHarmonyApp>> = const { RefCell::new(None) };
+}
+
+fn dispatch(event: InputEvent) {
+    let app = MOUSE_APP.with_borrow(|app| app.clone());
+    if let Some(app) = app {
+        // nothing can take the error here, the panic is reported by the panic hook
+        let _ = catch_panic("mouse_event", || {
+            if let Some(ref mut h) = *app.event_loop.borrow_mut() {
+                h(Event::Input(event))
+            }
+            Ok(())
+        });
+    }
+}
+
+fn scale() -> f32 {
+    MOUSE_APP
+        .with_borrow(|app| app.as_ref().map(|app| app.scale()))
+        .unwrap_or(1.0)
+}
+
+unsafe extern "C" fn dispatch_mouse_event(
+    xcomponent: *mut OH_NativeXComponent,
+    window: *mut c_void,
+) {
+    let mut raw = MaybeUninit::<OH_NativeXComponent_MouseEvent>::uninit();
+    if OH_NativeXComponent_GetMouseEvent(xcomponent, window, raw.as_mut_ptr()) != 0 {
+        return;
+    }
+    let raw = raw.assume_init();
+    let Some(action) = MouseAction::from_raw(raw.action) else {
+        return;
+    };
+    let physical_position = Point::new(raw.x, raw.y);
+    dispatch(InputEvent::Mouse(MouseEvent {
+        button: raw.button.into(),
+        action,
+        position: physical_position.to_logical(scale()),
+        physical_position,
+        screen_position: Point::new(raw.screenX, raw.screenY),
+        timestamp: raw.timestamp,
+    }));
+}
+
+unsafe extern "C" fn dispatch_hover_event(_xcomponent: *mut OH_NativeXComponent, is_hover: bool) {
+    dispatch(InputEvent::Hover(is_hover));
+}
+
+unsafe extern "C" fn dispatch_axis_event(
+    _xcomponent: *mut OH_NativeXComponent,
+    event: *mut ArkUI_UIInputEvent,
+    event_type: ArkUI_UIInputEvent_Type,
+) {
+    if event_type != ArkUI_UIInputEvent_Type_ARKUI_UIINPUTEVENT_TYPE_AXIS || event.is_null() {
+        return;
+    }
+    let physical_position = Point::new(
+        OH_ArkUI_PointerEvent_GetX(event),
+        OH_ArkUI_PointerEvent_GetY(event),
+    );
+    dispatch(InputEvent::Scroll(ScrollEvent {
+        delta_x: OH_ArkUI_AxisEvent_GetHorizontalAxisValue(event),
+        delta_y: OH_ArkUI_AxisEvent_GetVerticalAxisValue(event),
+        position: physical_position.to_logical(scale()),
+        physical_position,
+        timestamp: OH_ArkUI_UIInputEvent_GetEventTime(event),
+    }));
+}
+
+/// Register mouse, hover and axis callbacks for XComponent
+pub(crate) fn register_mouse_callbacks(
+    xcomponent: *mut OH_NativeXComponent,
+    app: OpenHarmonyApp,
+) -> Result<()> {
+    MOUSE_APP.with_borrow_mut(|f| *f = Some(app));
+
+    let cbs = Box::new(OH_NativeXComponent_MouseEvent_Callback {
+        DispatchMouseEvent: Some(dispatch_mouse_event),
+        DispatchHoverEvent: Some(dispatch_hover_event),
+    });
+    let ret = unsafe { OH_NativeXComponent_RegisterMouseEventCallback(xcomponent, Box::leak(cbs)) };
+    if ret != 0 {
+        return Err(Error::from_reason(
+            "XComponent register mouse callbacks failed",
+        ));
+    }
+
+    let ret = unsafe {
+        OH_NativeXComponent_RegisterUIInputEventCallback(
+            xcomponent,
+            Some(dispatch_axis_event),
+            ArkUI_UIInputEvent_Type_ARKUI_UIINPUTEVENT_TYPE_AXIS,
+        )
+    };
+    if ret != 0 {
+        return Err(Error::from_reason(
+            "XComponent register axis callback failed",
+        ));
+    }
+    Ok(())
+}
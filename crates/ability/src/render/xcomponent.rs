@@ -1,11 +1,12 @@
-use napi_ohos::threadsafe_function::ThreadsafeFunctionCallMode::NonBlocking;
+use std::time::Instant;
+
 use napi_ohos::{bindgen_prelude::ObjectRef, Env, Error, Result};
 use ohos_arkui_binding::{ArkUIHandle, RootNode, XComponent};
-use ohos_ime_binding::IME;
 
 use crate::{
-    create_permission_request_tsfn, input, set_helper, set_main_thread_env, Event, InputEvent,
-    IntervalInfo, OpenHarmonyApp, Rect, Size,
+    catch_panic, create_clipboard_read_tsfn, create_permission_request_tsfn,
+    create_permission_setting_tsfn, input, register_mouse_callbacks, set_helper,
+    set_main_thread_env, Event, InputEvent, IntervalInfo, OpenHarmonyApp, PointerEvent, Rect, Size,
 };
 
 /// create lifecycle object and return to arkts
@@ -19,7 +20,15 @@ pub fn render(
     set_main_thread_env(*env);
 
     // Initialize permission request threadsafe function
-    let _ = create_permission_request_tsfn(env);
+    if let Err(err) = create_permission_request_tsfn(env) {
+        log_error!("Failed to create permission request function: {}", err);
+    }
+    if let Err(err) = create_permission_setting_tsfn(env) {
+        log_error!("Failed to create permission setting function: {}", err);
+    }
+    if let Err(err) = create_clipboard_read_tsfn(env) {
+        log_error!("Failed to create clipboard read function: {}", err);
+    }
 
     let mut root = RootNode::new(slot);
     let xcomponent_native =
@@ -35,120 +44,171 @@ pub fn render(
     let xc = xcomponent.clone();
 
     let on_surface_created_app = app.clone();
-    let insert_text_app = app.clone();
     let redraw_app = app.clone();
 
-    let (
-        insert_text_callback_tsfn,
-        on_ime_hide_callback_tsfn,
-        on_backspace_callback_tsfn,
-        on_ime_enter_callback_tsfn,
-    ) = input::ime_ts_fn(env, app.clone())?;
+    input::create_ime_tsfn(env, app.clone())?;
 
     xcomponent.on_surface_created(move |xc_raw, win| {
-        {
-            let size = xc_raw.size(win).unwrap();
-            let offset = xc_raw.offset(win).unwrap();
-            on_surface_created_app.inner.write().unwrap().rect = Rect {
-                top: offset.y as _,
-                left: offset.x as _,
-                width: size.width as _,
-                height: size.height as _,
-            };
-        }
-        {
-            let raw_window = xc.native_window();
-            on_surface_created_app.inner.write().unwrap().raw_window = raw_window;
-            // We need to create IME instance when app is foucsed
-            let ime = IME::new(Default::default());
-
-            *on_surface_created_app.ime.borrow_mut() = Some(ime);
-        }
-
-        if let Some(b_ime) = insert_text_app.ime.borrow().as_ref() {
-            // // run in other thread
-            b_ime.insert_text(|s| {
-                insert_text_callback_tsfn.call(s, NonBlocking);
-            });
-            b_ime.on_status_change(|s| {
-                on_ime_hide_callback_tsfn.call(s.into(), NonBlocking);
-            });
-            b_ime.on_backspace(|len| {
-                on_backspace_callback_tsfn.call(len, NonBlocking);
-            });
-            b_ime.on_enter(|key| {
-                on_ime_enter_callback_tsfn.call(key as i32, NonBlocking);
-            });
-        }
-
-        {
-            if let Some(ref mut h) = *on_surface_created_app.event_loop.borrow_mut() {
-                h(Event::SurfaceCreate)
+        log_callback_error(catch_panic("on_surface_created", || {
+            {
+                let size = xc_raw.size(win).unwrap();
+                let offset = xc_raw.offset(win).unwrap();
+                log_info!("surface created: {}x{}", size.width, size.height);
+                on_surface_created_app.inner.write().unwrap().rect = Rect {
+                    top: offset.y as _,
+                    left: offset.x as _,
+                    width: size.width as _,
+                    height: size.height as _,
+                };
+            }
+            {
+                let mut inner = on_surface_created_app.inner.write().unwrap();
+                inner.raw_window = xc.native_window();
+                // A recreated surface starts with the default frame rate again.
+                inner.reapply_frame_rate();
+            }
+            {
+                if let Some(ref mut h) = *on_surface_created_app.event_loop.borrow_mut() {
+                    h(Event::SurfaceCreate)
+                }
             }
-        }
-
-        let inner_redraw_app = redraw_app.clone();
-        xc.on_frame_callback(move |_xcomponent, _time, _time_stamp| {
-            if let Some(ref mut h) = *inner_redraw_app.event_loop.borrow_mut() {
-                h(Event::WindowRedraw(IntervalInfo {
-                    time_stamp: _time_stamp as _,
-                    target_time_stamp: _time as _,
+
+            let inner_redraw_app = redraw_app.clone();
+            xc.on_frame_callback(move |_xcomponent, time_stamp, target_time_stamp| {
+                log_callback_error(catch_panic("on_frame_callback", || {
+                    let info = IntervalInfo {
+                        time_stamp: time_stamp as _,
+                        target_time_stamp: target_time_stamp as _,
+                    };
+                    let (redraw, stats) = {
+                        let mut inner = inner_redraw_app.inner.write().unwrap();
+                        let stats = inner.record_frame(&info);
+                        let redraw = inner.take_redraw(Instant::now());
+                        // unregister until the next request_redraw when nothing is pending
+                        inner.sync_frame_callback();
+                        (redraw, stats)
+                    };
+                    if let Some(ref mut h) = *inner_redraw_app.event_loop.borrow_mut() {
+                        if let Some(stats) = stats {
+                            h(Event::FrameStats(stats))
+                        }
+                        if redraw {
+                            h(Event::WindowRedraw(info))
+                        }
+                    }
+                    Ok(())
                 }))
+            })?;
+            {
+                let mut inner = redraw_app.inner.write().unwrap();
+                inner.set_frame_callback_armed(true);
+                inner.sync_frame_callback();
             }
             Ok(())
-        })?;
-        Ok(())
+        }))
     });
 
     let on_surface_destroyed_app = app.clone();
     xcomponent.on_surface_destroyed(move |_, _| {
-        if let Some(ref mut h) = *on_surface_destroyed_app.event_loop.borrow_mut() {
-            h(Event::SurfaceDestroy)
-        }
-        Ok(())
+        log_callback_error(catch_panic("on_surface_destroyed", || {
+            log_info!("surface destroyed");
+            // the editor belongs to this surface, a recreated surface starts text input again
+            on_surface_destroyed_app.stop_text_input();
+            if let Some(ref mut h) = *on_surface_destroyed_app.event_loop.borrow_mut() {
+                h(Event::SurfaceDestroy)
+            }
+            Ok(())
+        }))
     });
 
     let on_surface_changed_app = app.clone();
     xcomponent.on_surface_changed(move |xc, win| {
-        if let Some(ref mut h) = *on_surface_changed_app.event_loop.borrow_mut() {
-            let size = xc.size(win).unwrap();
-            let offset = xc.offset(win).unwrap();
-            {
-                on_surface_changed_app.inner.write().unwrap().rect = Rect {
-                    top: offset.y as _,
-                    left: offset.x as _,
-                    width: size.width as _,
-                    height: size.height as _,
+        log_callback_error(catch_panic("on_surface_changed", || {
+            if let Some(ref mut h) = *on_surface_changed_app.event_loop.borrow_mut() {
+                let size = xc.size(win).unwrap();
+                let offset = xc.offset(win).unwrap();
+                let scale = {
+                    let mut inner = on_surface_changed_app.inner.write().unwrap();
+                    inner.rect = Rect {
+                        top: offset.y as _,
+                        left: offset.x as _,
+                        width: size.width as _,
+                        height: size.height as _,
+                    };
+                    inner.scale()
                 };
+                log_debug!(
+                    "surface changed: {}x{}, scale {}",
+                    size.width,
+                    size.height,
+                    scale
+                );
+                let physical = Size::new(size.width as _, size.height as _);
+                h(Event::SurfaceResized {
+                    physical,
+                    logical: physical.to_logical(scale),
+                    scale,
+                })
             }
-            h(Event::WindowResize(Size {
-                width: size.width as _,
-                height: size.height as _,
-            }))
-        }
-        Ok(())
+            Ok(())
+        }))
     });
 
     let on_touch_event_app = app.clone();
-    xcomponent.on_touch_event(move |_, _, data| {
-        if let Some(ref mut h) = *on_touch_event_app.event_loop.borrow_mut() {
-            h(Event::Input(InputEvent::TouchEvent(data)))
-        }
-        Ok(())
+    xcomponent.on_touch_event(move |xc, _, data| {
+        log_callback_error(catch_panic("on_touch_event", || {
+            let pointer =
+                PointerEvent::from_touch(&data, on_touch_event_app.scale()).map(|mut e| {
+                    e.read_tilt(xc, &data);
+                    e
+                });
+            if let Some(ref mut h) = *on_touch_event_app.event_loop.borrow_mut() {
+                h(Event::Input(InputEvent::TouchEvent(data)));
+                if let Some(pointer) = pointer {
+                    h(Event::Input(InputEvent::Pointer(pointer)))
+                }
+            }
+            Ok(())
+        }))
     });
 
     let on_key_event_app = app.clone();
-    let _ = xcomponent.on_key_event(move |_, _, data| {
-        if let Some(ref mut h) = *on_key_event_app.event_loop.borrow_mut() {
-            h(Event::Input(InputEvent::KeyEvent(data)));
-        }
-        Ok(())
-    });
+    if let Err(err) = xcomponent.on_key_event(move |_, _, data| {
+        log_callback_error(catch_panic("on_key_event", || {
+            let keyboard = on_key_event_app
+                .inner
+                .write()
+                .unwrap()
+                .keyboard
+                .process(&data);
+            if let Some(ref mut h) = *on_key_event_app.event_loop.borrow_mut() {
+                h(Event::Input(InputEvent::KeyEvent(data)));
+                if let Some(keyboard) = keyboard {
+                    h(Event::Input(InputEvent::Keyboard(keyboard)));
+                }
+            }
+            Ok(())
+        }))
+    }) {
+        log_error!("Failed to register key event callback: {}", err);
+    }
 
     xcomponent.register_callback()?;
+    // mouse and axis input is optional, the surface works without it
+    if let Err(err) = register_mouse_callbacks(xcomponent.raw(), app.clone()) {
+        log_warn!("Failed to register mouse callbacks: {}", err);
+    }
 
     root.mount(xcomponent_native)
         .map_err(|e| Error::from_reason(e.reason.to_string()))?;
 
     Ok(root)
 }
+
+/// The binding unwraps the result of its callbacks inside `extern "C"`, an `Err` would abort
+fn log_callback_error(result: Result<()>) -> Result<()> {
+    if let Err(err) = result {
+        log_error!("XComponent callback failed: {}", err);
+    }
+    Ok(())
+}
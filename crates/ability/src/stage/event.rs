@@ -1,3 +1,4 @@
+#[derive(Debug)]
 pub enum StageEventType {
     Shown = 1,
     Active,
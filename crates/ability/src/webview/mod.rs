@@ -6,7 +6,10 @@ use napi_ohos::{
     Error, Result,
 };
 
-use crate::helper::{DownloadStartResult, WebViewInitData, WebViewStyle, Webview};
+use crate::{
+    catch_panic,
+    helper::{DownloadStartResult, WebViewInitData, WebViewStyle, Webview},
+};
 
 mod drag;
 
@@ -218,34 +221,38 @@ impl WebViewBuilder {
                 #[cfg(feature = "drag_and_drop")]
                 let on_drag_and_drop = self.on_drag_and_drop.and_then(|handler| {
                     env.create_function_from_closure("on_drag_and_drop", move |ctx| {
-                        let ret = ctx.try_get::<String>(1)?;
-                        let ret = match ret {
-                            Either::A(s) => s,
-                            Either::B(_ret) => String::new(),
-                        };
-                        handler(ret);
-                        Ok(())
+                        catch_panic("on_drag_and_drop", || {
+                            let ret = ctx.try_get::<String>(1)?;
+                            let ret = match ret {
+                                Either::A(s) => s,
+                                Either::B(_ret) => String::new(),
+                            };
+                            handler(ret);
+                            Ok(())
+                        })
                     })
                     .ok()
                 });
 
                 let on_download_start = self.on_download_start.and_then(|handler| {
                     env.create_function_from_closure("on_download_start", move |ctx| {
-                        let origin_url = ctx.try_get::<String>(1)?;
-                        let temp_path = ctx.try_get::<String>(2)?;
-                        let origin_url_str = match origin_url {
-                            Either::A(s) => s,
-                            Either::B(_ret) => String::new(),
-                        };
-                        let temp_path_str = match temp_path {
-                            Either::A(s) => s,
-                            Either::B(_ret) => String::new(),
-                        };
-                        let mut temp_path = PathBuf::from(temp_path_str);
-                        let ret = handler(origin_url_str, &mut temp_path);
-                        Ok(DownloadStartResult {
-                            allow: ret,
-                            temp_path: Some(temp_path.to_string_lossy().to_string()),
+                        catch_panic("on_download_start", || {
+                            let origin_url = ctx.try_get::<String>(1)?;
+                            let temp_path = ctx.try_get::<String>(2)?;
+                            let origin_url_str = match origin_url {
+                                Either::A(s) => s,
+                                Either::B(_ret) => String::new(),
+                            };
+                            let temp_path_str = match temp_path {
+                                Either::A(s) => s,
+                                Either::B(_ret) => String::new(),
+                            };
+                            let mut temp_path = PathBuf::from(temp_path_str);
+                            let ret = handler(origin_url_str, &mut temp_path);
+                            Ok(DownloadStartResult {
+                                allow: ret,
+                                temp_path: Some(temp_path.to_string_lossy().to_string()),
+                            })
                         })
                     })
                     .ok()
@@ -253,49 +260,55 @@ impl WebViewBuilder {
 
                 let on_download_end = self.on_download_end.and_then(|handler| {
                     env.create_function_from_closure("on_download_end", move |ctx| {
-                        let origin_url = ctx.try_get::<String>(1)?;
-                        let temp_path = ctx.try_get::<String>(2)?;
-                        let success = ctx.try_get::<bool>(3)?;
-                        let origin_url_str = match origin_url {
-                            Either::A(s) => s,
-                            Either::B(_ret) => String::new(),
-                        };
-                        let temp_path_str = match temp_path {
-                            Either::A(s) => Some(PathBuf::from(s)),
-                            Either::B(_ret) => None,
-                        };
-                        let success_bool = match success {
-                            Either::A(ret) => ret,
-                            Either::B(_ret) => false,
-                        };
-                        handler(origin_url_str, temp_path_str, success_bool);
-                        Ok(())
+                        catch_panic("on_download_end", || {
+                            let origin_url = ctx.try_get::<String>(1)?;
+                            let temp_path = ctx.try_get::<String>(2)?;
+                            let success = ctx.try_get::<bool>(3)?;
+                            let origin_url_str = match origin_url {
+                                Either::A(s) => s,
+                                Either::B(_ret) => String::new(),
+                            };
+                            let temp_path_str = match temp_path {
+                                Either::A(s) => Some(PathBuf::from(s)),
+                                Either::B(_ret) => None,
+                            };
+                            let success_bool = match success {
+                                Either::A(ret) => ret,
+                                Either::B(_ret) => false,
+                            };
+                            handler(origin_url_str, temp_path_str, success_bool);
+                            Ok(())
+                        })
                     })
                     .ok()
                 });
 
                 let on_navigation_request = self.on_navigation_request.and_then(|handler| {
                     env.create_function_from_closure("on_navigation_request", move |ctx| {
-                        let ret = ctx.try_get::<String>(1)?;
-                        let ret = match ret {
-                            Either::A(s) => s,
-                            Either::B(_ret) => String::new(),
-                        };
-                        let ret = handler(ret);
-                        Ok(ret)
+                        catch_panic("on_navigation_request", || {
+                            let ret = ctx.try_get::<String>(1)?;
+                            let ret = match ret {
+                                Either::A(s) => s,
+                                Either::B(_ret) => String::new(),
+                            };
+                            let ret = handler(ret);
+                            Ok(ret)
+                        })
                     })
                     .ok()
                 });
 
                 let on_title_change = self.on_title_change.and_then(|handler| {
                     env.create_function_from_closure("on_title_change", move |ctx| {
-                        let ret = ctx.try_get::<String>(1)?;
-                        let ret = match ret {
-                            Either::A(s) => s,
-                            Either::B(_ret) => String::new(),
-                        };
-                        handler(ret);
-                        Ok(())
+                        catch_panic("on_title_change", || {
+                            let ret = ctx.try_get::<String>(1)?;
+                            let ret = match ret {
+                                Either::A(s) => s,
+                                Either::B(_ret) => String::new(),
+                            };
+                            handler(ret);
+                            Ok(())
+                        })
                     })
                     .ok()
                 });
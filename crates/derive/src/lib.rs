@@ -105,9 +105,11 @@ pub fn ability(attr: TokenStream, item: TokenStream) -> TokenStream {
             helper: napi_ohos::bindgen_prelude::ObjectRef,
             #[napi(ts_arg_type = "NodeContent")] slot: openharmony_ability::arkui::ArkUIHandle,
         ) -> napi_ohos::Result<()> {
-            let root = openharmony_ability::render(env, helper, slot, (*APP).clone())?;
-            ROOT_NODE.replace(Some(root));
-            Ok(())
+            openharmony_ability::catch_panic("render", || {
+                let root = openharmony_ability::render(env, helper, slot, (*APP).clone())?;
+                ROOT_NODE.replace(Some(root));
+                Ok(())
+            })
         }
     };
 
@@ -144,8 +146,46 @@ pub fn ability(attr: TokenStream, item: TokenStream) -> TokenStream {
             #protocol_registrations_apply
 
             #[napi_derive_ohos::napi]
-            pub fn on_back_press_intercept() -> bool {
-                (*APP).get_back_press_interceptor()
+            pub fn on_back_press_intercept() -> napi_ohos::Result<bool> {
+                openharmony_ability::catch_panic("on_back_press_intercept", || {
+                    Ok((*APP).get_back_press_interceptor())
+                })
+            }
+
+            #[napi_derive_ohos::napi]
+            pub fn on_back_gesture_start(edge: i32) -> napi_ohos::Result<()> {
+                openharmony_ability::catch_panic("on_back_gesture_start", || {
+                    (*APP).dispatch_back_gesture(openharmony_ability::BackGestureEvent::Started {
+                        edge: edge.into(),
+                    });
+                    Ok(())
+                })
+            }
+
+            #[napi_derive_ohos::napi]
+            pub fn on_back_gesture_progress(progress: f64, edge: i32) -> napi_ohos::Result<()> {
+                openharmony_ability::catch_panic("on_back_gesture_progress", || {
+                    (*APP).dispatch_back_gesture(openharmony_ability::BackGestureEvent::Progressed {
+                        progress: progress as f32,
+                        edge: edge.into(),
+                    });
+                    Ok(())
+                })
+            }
+
+            #[napi_derive_ohos::napi]
+            pub fn on_back_gesture_cancel() -> napi_ohos::Result<()> {
+                openharmony_ability::catch_panic("on_back_gesture_cancel", || {
+                    (*APP).dispatch_back_gesture(openharmony_ability::BackGestureEvent::Cancelled);
+                    Ok(())
+                })
+            }
+
+            #[napi_derive_ohos::napi]
+            pub fn on_back_gesture_commit() -> napi_ohos::Result<bool> {
+                openharmony_ability::catch_panic("on_back_gesture_commit", || {
+                    Ok((*APP).commit_back_gesture())
+                })
             }
 
             #[napi_derive_ohos::napi]
@@ -154,13 +194,15 @@ pub fn ability(attr: TokenStream, item: TokenStream) -> TokenStream {
                 #[napi(ts_arg_type = "AbilityInitContext")]
                 context: Option<napi_ohos::bindgen_prelude::Object<'a>>,
             ) -> napi_ohos::Result<openharmony_ability::ApplicationLifecycle<'a>> {
-                let init_context = openharmony_ability::AbilityInitContext::from_object(context.as_ref())?;
-                let resource_manager = openharmony_ability::ResourceManager::from_init_context(*env, context.as_ref())?;
-                (*APP).set_init_context(init_context);
-                (*APP).set_resource_manager(resource_manager);
-                let lifecycle_handle = openharmony_ability::create_lifecycle_handle(env, (*APP).clone())?;
-                #fn_name((*APP).clone());
-                Ok(lifecycle_handle)
+                openharmony_ability::catch_panic("init", || {
+                    let init_context = openharmony_ability::AbilityInitContext::from_object(context.as_ref())?;
+                    let resource_manager = openharmony_ability::ResourceManager::from_init_context(*env, context.as_ref())?;
+                    (*APP).set_init_context(init_context);
+                    (*APP).set_resource_manager(resource_manager);
+                    let lifecycle_handle = openharmony_ability::create_lifecycle_handle(env, (*APP).clone())?;
+                    #fn_name((*APP).clone());
+                    Ok(lifecycle_handle)
+                })
             }
 
             #render